@@ -1,36 +1,40 @@
-console.log("Hello from background service worker. 👋");
+// Chrome runs this as a service worker, which loads the polyfill itself; Firefox lists it before this script in the manifest.
+if (typeof browser === "undefined") {
+    importScripts("browser-polyfill.js");
+}
 
-chrome.runtime.onInstalled.addListener(() => {
-    // default
-    chrome.action.disable();
+console.log("Hello from background. 👋");
 
-  chrome.declarativeContent.onPageChanged.removeRules(undefined, () => {
-    let exampleRule = {
-      conditions: [
-        new chrome.declarativeContent.PageStateMatcher({
-          pageUrl: {hostSuffix: '.powerlanguage.co.uk'},
-        })
-      ],
-      actions: [new chrome.declarativeContent.ShowAction()],
-    };
+// Keep in step with `Site::from_url` in src/sites.rs.
+const PAGES = [
+    { host: "nytimes.com", path: "/games/wordle" },
+    { host: "powerlanguage.co.uk" },
+    { host: "quordle.com" },
+    { host: "merriam-webster.com", path: "/games/quordle" },
+    { host: "wordleunlimited.org" },
+];
 
-    // Finally, apply our new array of rules
-    let rules = [exampleRule];
-    chrome.declarativeContent.onPageChanged.addRules(rules);
+function is_puzzle_page(url) {
+    let page;
+    try {
+        page = new URL(url);
+    } catch {
+        return false;
+    }
 
-    console.log("Enabled toolbar action on puzzle sites.");
-  });
-});
+    return PAGES.some(({ host, path }) =>
+        (page.hostname == host || page.hostname.endsWith(`.${host}`)) && page.pathname.startsWith(path || "/"));
+}
 
-function perform_next_guess() {
-    console.log("@perform_next_guess", wasm);
-    wasm.perform_next_guess();
+// Only puzzle pages get an active toolbar button. Tabs elsewhere don't share their URL with us, so they stay disabled.
+function update_action(tab) {
+    if (is_puzzle_page(tab.url)) {
+        browser.action.enable(tab.id);
+    } else {
+        browser.action.disable(tab.id);
+    }
 }
 
-chrome.action.onClicked.addListener(async (tab) => {
-  chrome.scripting.executeScript({
-    target: {tabId: tab.id},
-    func: perform_next_guess,
-    args: [],
-  });
-});
\ No newline at end of file
+browser.runtime.onInstalled.addListener(() => browser.action.disable());
+browser.tabs.onUpdated.addListener((tab_id, change, tab) => update_action(tab));
+browser.tabs.onActivated.addListener(({ tabId }) => browser.tabs.get(tabId).then(update_action));
@@ -0,0 +1,4 @@
+// Firefox and Safari provide the WebExtension `browser` namespace; Chrome only has `chrome`. Under Manifest V3 Chrome's APIs return promises just like `browser`'s, so the namespace is all that needs filling in. Load this first and use `browser` everywhere.
+if (typeof globalThis.browser === "undefined") {
+    globalThis.browser = chrome;
+}
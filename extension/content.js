@@ -1,212 +1,206 @@
 console.log("Helple at your service. 🙇🏼");
-var wasmPath = chrome.runtime.getURL("helple_bg.wasm");
 
-setTimeout(async () => {
-  wasm = await init(wasmPath);
-}, 1);
+// helple.js is generated by `cargo xtask build` and defines `wasm_bindgen`, which loads the module and holds its exports.
+const ready = wasm_bindgen({ module_or_path: browser.runtime.getURL("helple_bg.wasm") });
 
-//
-//   Contents of pkg/helple.js with import&exports removed. 
-//   Generated with `wasm-pack build --target web`
-//
-let wasm;
+// How long to wait for a guess's tiles to be revealed before reading the board again.
+const REVEAL_DELAY = 2500;
 
-const heap = new Array(32).fill(undefined);
+// How long a submitted guess may take to show up on the board before we check whether the game refused the word.
+const REJECT_TIMEOUT = 3000;
 
-heap.push(undefined, null, true, false);
+// What auto-solve is doing, shown by the popup: idle, playing, solved, failed or cancelled.
+let auto_solve = { status: "idle", played: [] };
+let cancelled = false;
+// The ranking auto-solve is waiting on, so Cancel can stop it.
+let ranking = null;
 
-function getObject(idx) { return heap[idx]; }
-
-let WASM_VECTOR_LEN = 0;
+function sleep(ms) {
+    return new Promise(resolve => setTimeout(resolve, ms));
+}
 
-let cachegetUint8Memory0 = null;
-function getUint8Memory0() {
-    if (cachegetUint8Memory0 === null || cachegetUint8Memory0.buffer !== wasm.memory.buffer) {
-        cachegetUint8Memory0 = new Uint8Array(wasm.memory.buffer);
+// The options saved by options.js, checked by the wasm side. Falls back to the defaults if they don't pass.
+async function load_config() {
+    await ready;
+    let { config } = await browser.storage.sync.get("config");
+    let report = wasm_bindgen.validate_config(config || {});
+    if (report.error) {
+        console.warn("Ignoring saved options:", report.error);
+        return wasm_bindgen.validate_config({}).config;
     }
-    return cachegetUint8Memory0;
+    return report.config;
 }
 
-let cachedTextEncoder = new TextEncoder('utf-8');
-
-const encodeString = (typeof cachedTextEncoder.encodeInto === 'function'
-    ? function (arg, view) {
-    return cachedTextEncoder.encodeInto(arg, view);
+// Ranking guesses runs in worker.js so slow strategies don't freeze the game. A content script can't start a worker from the extension, so solver.html does it from a hidden frame and passes its messages through a MessageChannel.
+let solver = null;
+
+function load_solver() {
+    if (!solver) {
+        solver = new Promise(resolve => {
+            let frame = document.createElement("iframe");
+            frame.src = browser.runtime.getURL("solver.html");
+            frame.hidden = true;
+            frame.addEventListener("load", () => {
+                let channel = new MessageChannel();
+                // The extension's origin, without getURL's trailing slash.
+                let origin = browser.runtime.getURL("").slice(0, -1);
+                frame.contentWindow.postMessage("helple-connect", origin, [channel.port2]);
+                resolve(new HelpleWorker(channel.port1));
+            }, { once: true });
+            document.documentElement.appendChild(frame);
+        });
+    }
+    return solver;
 }
-    : function (arg, view) {
-    const buf = cachedTextEncoder.encode(arg);
-    view.set(buf);
-    return {
-        read: arg.length,
-        written: buf.length
-    };
-});
-
-function passStringToWasm0(arg, malloc, realloc) {
 
-    if (realloc === undefined) {
-        const buf = cachedTextEncoder.encode(arg);
-        const ptr = malloc(buf.length);
-        getUint8Memory0().subarray(ptr, ptr + buf.length).set(buf);
-        WASM_VECTOR_LEN = buf.length;
-        return ptr;
+// How the game stands and, while it's still going, the best word not in `rejected`, as `{status, word}`.
+async function next_step(config, rejected) {
+    let { progress, state } = wasm_bindgen.board_state(config);
+    if (progress.status != "playing") {
+        return progress;
     }
 
-    let len = arg.length;
-    let ptr = malloc(len);
-
-    const mem = getUint8Memory0();
-
-    let offset = 0;
-
-    for (; offset < len; offset++) {
-        const code = arg.charCodeAt(offset);
-        if (code > 0x7F) break;
-        mem[ptr + offset] = code;
+    ranking = (await load_solver()).suggest(state, rejected, 1);
+    let report = await ranking.result;
+    if (cancelled) {
+        throw "cancelled";
     }
-
-    if (offset !== len) {
-        if (offset !== 0) {
-            arg = arg.slice(offset);
-        }
-        ptr = realloc(ptr, len, len = offset + arg.length * 3);
-        const view = getUint8Memory0().subarray(ptr + offset, ptr + len);
-        const ret = encodeString(arg, view);
-
-        offset += ret.written;
+    if (report.suggestions.length == 0) {
+        return { status: "failed", reason: "No guess available" };
     }
-
-    WASM_VECTOR_LEN = offset;
-    return ptr;
+    return { status: "playing", word: report.suggestions[0].word };
 }
 
-let cachegetInt32Memory0 = null;
-function getInt32Memory0() {
-    if (cachegetInt32Memory0 === null || cachegetInt32Memory0.buffer !== wasm.memory.buffer) {
-        cachegetInt32Memory0 = new Int32Array(wasm.memory.buffer);
-    }
-    return cachegetInt32Memory0;
+// Words the game has refused, e.g. "Not in word list". Kept per site, since each has its own word list, until cleared on the options page.
+function rejected_key() {
+    return `rejected:${location.hostname}`;
 }
 
-let heap_next = heap.length;
-
-function dropObject(idx) {
-    if (idx < 36) return;
-    heap[idx] = heap_next;
-    heap_next = idx;
+async function load_rejected() {
+    let key = rejected_key();
+    let stored = await browser.storage.local.get(key);
+    return stored[key] || [];
 }
 
-function takeObject(idx) {
-    const ret = getObject(idx);
-    dropObject(idx);
-    return ret;
+async function remember_rejected(word) {
+    let rejected = await load_rejected();
+    if (!rejected.includes(word)) {
+        await browser.storage.local.set({ [rejected_key()]: [...rejected, word] });
+    }
 }
 
-let cachedTextDecoder = new TextDecoder('utf-8', { ignoreBOM: true, fatal: true });
-
-cachedTextDecoder.decode();
-
-function getStringFromWasm0(ptr, len) {
-    return cachedTextDecoder.decode(getUint8Memory0().subarray(ptr, ptr + len));
+async function type_word(word, config) {
+    for (let letter of word) {
+        wasm_bindgen.press_key(letter);
+        await sleep(config.typing_delay);
+    }
+    if (config.auto_submit) {
+        wasm_bindgen.press_key("Enter");
+    }
 }
 
-function addHeapObject(obj) {
-    if (heap_next === heap.length) heap.push(heap.length + 1);
-    const idx = heap_next;
-    heap_next = heap[idx];
-
-    heap[idx] = obj;
-    return idx;
-}
-/**
-*/
-function perform_next_guess() {
-    wasm.perform_next_guess();
+async function clear_word(word, config) {
+    for (let _ of word) {
+        wasm_bindgen.press_key("Backspace");
+        await sleep(config.typing_delay);
+    }
 }
 
-/**
-* @returns {string}
-*/
-function run() {
-    try {
-        const retptr = wasm.__wbindgen_add_to_stack_pointer(-16);
-        wasm.run(retptr);
-        var r0 = getInt32Memory0()[retptr / 4 + 0];
-        var r1 = getInt32Memory0()[retptr / 4 + 1];
-        return getStringFromWasm0(r0, r1);
-    } finally {
-        wasm.__wbindgen_add_to_stack_pointer(16);
-        wasm.__wbindgen_free(r0, r1);
+// Waits until the board shows more guesses than `count`, giving up after `timeout` ms if one is given. Returns whether a guess showed up.
+async function wait_for_guess(count, timeout) {
+    let waited = 0;
+    while (wasm_bindgen.read_board().guesses.length <= count) {
+        if (cancelled || (timeout !== undefined && waited >= timeout)) {
+            return false;
+        }
+        await sleep(250);
+        waited += 250;
     }
+    return true;
 }
 
-async function load(module, imports) {
-    if (typeof Response === 'function' && module instanceof Response) {
-        if (typeof WebAssembly.instantiateStreaming === 'function') {
-            try {
-                return await WebAssembly.instantiateStreaming(module, imports);
-
-            } catch (e) {
-                if (module.headers.get('Content-Type') != 'application/wasm') {
-                    console.warn("`WebAssembly.instantiateStreaming` failed because your server does not serve wasm with `application/wasm` MIME type. Falling back to `WebAssembly.instantiate` which is slower. Original error:\n", e);
-
-                } else {
-                    throw e;
-                }
-            }
+// Types the next guess, moving on to the next best word whenever the game refuses one. Returns the step, see `next_step`.
+async function play_next_guess(config) {
+    while (true) {
+        let count = wasm_bindgen.read_board().guesses.length;
+        let step = await next_step(config, await load_rejected());
+        if (!step.word) {
+            return step;
         }
 
-        const bytes = await module.arrayBuffer();
-        return await WebAssembly.instantiate(bytes, imports);
-
-    } else {
-        const instance = await WebAssembly.instantiate(module, imports);
-
-        if (instance instanceof WebAssembly.Instance) {
-            return { instance, module };
+        await type_word(step.word, config);
+        // Without auto-submit it's up to the player to press Enter, so there's no refusal to watch for.
+        if (!config.auto_submit || await wait_for_guess(count, REJECT_TIMEOUT) || cancelled) {
+            return step;
+        }
 
-        } else {
-            return instance;
+        // A refused word stays typed in its row. Anything else, e.g. a slow reveal, isn't a refusal.
+        if (wasm_bindgen.typed_word() != step.word) {
+            return step;
         }
-    }
-}
 
-async function init(input) {
-    const imports = {};
-    imports.wbg = {};
-    imports.wbg.__wbindgen_json_serialize = function(arg0, arg1) {
-        const obj = getObject(arg1);
-        var ret = JSON.stringify(obj === undefined ? null : obj);
-        var ptr0 = passStringToWasm0(ret, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
-        var len0 = WASM_VECTOR_LEN;
-        getInt32Memory0()[arg0 / 4 + 1] = len0;
-        getInt32Memory0()[arg0 / 4 + 0] = ptr0;
-    };
-    imports.wbg.__wbg_getcurrentpuzzlestate_63b7a1016e50b3b1 = function() {
-        var ret = get_current_puzzle_state();
-        return addHeapObject(ret);
-    };
-    imports.wbg.__wbindgen_object_drop_ref = function(arg0) {
-        takeObject(arg0);
-    };
-    imports.wbg.__wbg_consolelog_4861b8a667e0df60 = function(arg0, arg1) {
-        console_log(getStringFromWasm0(arg0, arg1));
-    };
-    imports.wbg.__wbg_performguess_4208f7f71bb74beb = function(arg0, arg1) {
-        perform_guess(getStringFromWasm0(arg0, arg1));
-    };
-
-    if (typeof input === 'string' || (typeof Request === 'function' && input instanceof Request) || (typeof URL === 'function' && input instanceof URL)) {
-        input = fetch(input);
+        console.log(`The game refused ${step.word}, trying another word.`);
+        await remember_rejected(step.word);
+        await clear_word(step.word, config);
     }
+}
 
+async function run_auto_solve() {
+    cancelled = false;
+    auto_solve = { status: "playing", played: [] };
+    let config = await load_config();
+
+    while (!cancelled) {
+        let step;
+        try {
+            let count = wasm_bindgen.read_board().guesses.length;
+            step = await play_next_guess(config);
+            if (step.word) {
+                auto_solve.played.push(step.word);
+                await wait_for_guess(count);
+            }
+        } catch (error) {
+            if (!cancelled) {
+                auto_solve = { ...auto_solve, status: "failed", reason: String(error) };
+                return;
+            }
+            break;
+        }
 
+        if (step.status != "playing") {
+            auto_solve = { ...auto_solve, ...step };
+            return;
+        }
 
-    const { instance, module } = await load(await input, imports);
-
-    wasm = instance.exports;
-    init.__wbindgen_wasm_module = module;
+        await sleep(REVEAL_DELAY);
+    }
 
-    return wasm;
+    auto_solve = { ...auto_solve, status: "cancelled" };
 }
 
+browser.runtime.onMessage.addListener((message, sender, respond) => {
+    switch (message.type) {
+        case "play_once":
+            // Both would type into the same row at once.
+            if (auto_solve.status == "playing") {
+                respond({ status: "failed", reason: "Auto-solve is already playing" });
+                return;
+            }
+            cancelled = false;
+            load_config().then(play_next_guess).then(respond, error => respond({ status: "failed", reason: String(error) }));
+            // Keeps `respond` usable once the guess has been typed.
+            return true;
+        case "auto_solve":
+            if (auto_solve.status != "playing") {
+                run_auto_solve();
+            }
+            break;
+        case "cancel":
+            cancelled = true;
+            if (ranking) {
+                ranking.cancel();
+            }
+            break;
+    }
+    respond(auto_solve);
+});
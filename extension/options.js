@@ -0,0 +1,72 @@
+// Options are checked by `validate_config` on the wasm side before they're saved, and again by content.js when they're used.
+const ready = wasm_bindgen({ module_or_path: "helple_bg.wasm" });
+
+function show(config) {
+    document.getElementById("typing_delay").value = config.typing_delay;
+    document.getElementById("auto_submit").checked = config.auto_submit;
+    document.getElementById("show_overlay").checked = config.show_overlay;
+    document.getElementById("hard_mode").checked = config.hard_mode;
+    document.getElementById("strategy").value = config.strategy;
+    document.getElementById("opening").value = config.opening || "";
+}
+
+function read_form() {
+    return {
+        typing_delay: Number(document.getElementById("typing_delay").value),
+        auto_submit: document.getElementById("auto_submit").checked,
+        show_overlay: document.getElementById("show_overlay").checked,
+        hard_mode: document.getElementById("hard_mode").checked,
+        strategy: document.getElementById("strategy").value,
+        opening: document.getElementById("opening").value,
+    };
+}
+
+document.getElementById("options").addEventListener("submit", async event => {
+    event.preventDefault();
+    await ready;
+
+    let report = wasm_bindgen.validate_config(read_form());
+    document.getElementById("error").textContent = report.error || "";
+    document.getElementById("saved").textContent = "";
+    if (report.error) {
+        return;
+    }
+
+    await browser.storage.sync.set({ config: report.config });
+    show(report.config);
+    document.getElementById("saved").textContent = "Saved.";
+});
+
+// Refused words are kept by content.js under `rejected:<host>` in local storage.
+async function show_rejected() {
+    let stored = await browser.storage.local.get(null);
+    let sites = Object.keys(stored).filter(key => key.startsWith("rejected:")).sort();
+    let list = document.getElementById("rejected");
+
+    if (sites.length == 0) {
+        let item = document.createElement("li");
+        item.textContent = "None yet.";
+        list.replaceChildren(item);
+        return;
+    }
+
+    list.replaceChildren(...sites.map(key => {
+        let item = document.createElement("li");
+        let clear = document.createElement("button");
+        clear.textContent = "Clear";
+        clear.addEventListener("click", async () => {
+            await browser.storage.local.remove(key);
+            show_rejected();
+        });
+        item.append(`${key.slice("rejected:".length)}: ${stored[key].join(", ")} `, clear);
+        return item;
+    }));
+}
+
+show_rejected();
+
+ready.then(async () => {
+    let { config } = await browser.storage.sync.get("config");
+    let report = wasm_bindgen.validate_config(config || {});
+    show(report.config || wasm_bindgen.validate_config({}).config);
+});
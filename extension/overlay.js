@@ -0,0 +1,119 @@
+// A panel on the game page with the candidate count, helple's top suggestions and the words still left. Loaded after content.js, whose `ready`, `load_config`, `load_rejected` and `load_solver` it shares.
+
+// How many suggestions the panel lists.
+const OVERLAY_SUGGESTIONS = 5;
+
+const OVERLAY_STYLE = `
+    :host { position: fixed; top: 64px; right: 16px; z-index: 10000; }
+    .panel { width: 220px; max-height: 70vh; overflow-y: auto; padding: 10px 12px;
+             background: #fff; color: #121213; border: 1px solid #d3d6da; border-radius: 6px;
+             box-shadow: 0 4px 16px rgba(0, 0, 0, 0.15); font: 13px sans-serif; }
+    .header { display: flex; justify-content: space-between; font-weight: bold; }
+    .close { border: none; background: none; cursor: pointer; font-size: 14px; }
+    ol { padding-left: 1.4em; margin: 0.6em 0; }
+    .score { color: #787c7e; }
+    .words { font-family: monospace; line-height: 1.5; word-spacing: 0.4em; }
+    .error { color: #b00020; }
+`;
+
+function create_overlay() {
+    let host = document.createElement("helple-overlay");
+    // A shadow root keeps the game's styles and ours apart.
+    let root = host.attachShadow({ mode: "open" });
+    root.innerHTML = `
+        <style>${OVERLAY_STYLE}</style>
+        <div class="panel">
+            <div class="header"><span>helple</span><button class="close" title="Hide">✕</button></div>
+            <p class="count"></p>
+            <ol class="suggestions"></ol>
+            <details><summary>Words left</summary><p class="words"></p></details>
+            <p class="error"></p>
+        </div>`;
+    root.querySelector(".close").addEventListener("click", () => host.remove());
+    document.body.appendChild(host);
+    return root;
+}
+
+function render_overlay(root, report) {
+    root.querySelector(".error").textContent = report.error || "";
+    root.querySelector(".count").textContent = report.error ? ""
+        : report.candidate_count == 1 ? "1 word left" : `${report.candidate_count} words left`;
+
+    let list = root.querySelector(".suggestions");
+    list.replaceChildren(...report.suggestions.map(suggestion => {
+        let item = document.createElement("li");
+        let score = document.createElement("span");
+        score.className = "score";
+        score.textContent = ` ${suggestion.score.toFixed(2)}`;
+        item.append(suggestion.word, score);
+        return item;
+    }));
+
+    root.querySelector(".words").textContent = report.remaining.join(" ");
+}
+
+async function show_overlay() {
+    let config = await load_config();
+    if (!config.show_overlay) {
+        return;
+    }
+
+    let root = create_overlay();
+    // Rank again in the worker only when the guesses or rejected words have changed, abandoning any ranking it is still on, as www/app.js does.
+    let shown = null;
+    let job = null;
+    let show_error = error => render_overlay(root, { error: String(error), suggestions: [], remaining: [] });
+    let refresh = async () => {
+        let solver = await load_solver();
+        let rejected = await load_rejected();
+        let board;
+        try {
+            board = wasm_bindgen.board_state(config);
+        } catch (error) {
+            shown = null;
+            show_error(error);
+            return;
+        }
+
+        let state = JSON.stringify([board.state.guesses, rejected]);
+        if (state === shown) {
+            return;
+        }
+        shown = state;
+
+        if (job) {
+            job.cancel();
+        }
+        let current = solver.suggest(board.state, rejected, OVERLAY_SUGGESTIONS, progress => {
+            root.querySelector(".count").textContent = `Ranking… ${Math.round(progress * 100)}%`;
+        });
+        job = current;
+        current.result.then(
+            report => {
+                if (job == current) {
+                    render_overlay(root, report);
+                }
+            },
+            error => {
+                if (job == current && error != "cancelled") {
+                    show_error(error);
+                }
+            },
+        );
+    };
+
+    // Re-read the board whenever the page changes, once it has settled. Boards inside shadow roots don't notify the observer, so keys and clicks also count, with time for the tiles to be revealed.
+    let timer = null;
+    let schedule = delay => {
+        clearTimeout(timer);
+        timer = setTimeout(refresh, delay);
+    };
+    new MutationObserver(() => schedule(300))
+        .observe(document.body, { subtree: true, childList: true, attributes: true, characterData: true });
+    document.addEventListener("keyup", () => schedule(REVEAL_DELAY));
+    document.addEventListener("click", () => schedule(REVEAL_DELAY));
+
+    refresh();
+}
+
+show_overlay();
@@ -0,0 +1,107 @@
+// The popup can't see the game, so anything that needs the board or the wasm module runs in the page.
+async function run_in_page(func) {
+    let [tab] = await browser.tabs.query({active: true, currentWindow: true});
+    let [result] = await browser.scripting.executeScript({
+        target: {tabId: tab.id},
+        func: func,
+    });
+    return result.result;
+}
+
+function page_hints() {
+    try {
+        return wasm_bindgen.hints(wasm_bindgen.read_board());
+    } catch (error) {
+        return { hints: [], error: String(error) };
+    }
+}
+
+function describe(hint) {
+    switch (hint.kind) {
+        case "candidate_count":
+            return hint.count == 1 ? "Only one word is left." : `${hint.count} words are still possible.`;
+        case "letter":
+            return hint.certain
+                ? `The word contains ${hint.letter}.`
+                : `The word likely contains ${hint.letter}: ${hint.candidates} of the words left do.`;
+        case "position":
+            return hint.certain
+                ? `Letter ${hint.position} is ${hint.letter}.`
+                : `Letter ${hint.position} is likely ${hint.letter}: it is in ${hint.candidates} of the words left.`;
+        case "word":
+            return `Try ${hint.word}.`;
+    }
+}
+
+// Auto-solve runs in content.js so it carries on when the popup closes; the popup only starts, cancels and watches it.
+async function send_to_page(type) {
+    let [tab] = await browser.tabs.query({active: true, currentWindow: true});
+    return browser.tabs.sendMessage(tab.id, {type});
+}
+
+function describe_auto_solve(state) {
+    let played = state.played.join(", ");
+    switch (state.status) {
+        case "playing":
+            return played ? `Playing… ${played}` : "Playing…";
+        case "solved":
+            return `Solved ${state.word} in ${state.guesses}!`;
+        case "failed":
+            return `Stopped: ${state.reason}.`;
+        case "cancelled":
+            return "Cancelled.";
+        default:
+            return "";
+    }
+}
+
+function show_auto_solve(state) {
+    let playing = state.status == "playing";
+    document.getElementById("solve").hidden = playing;
+    document.getElementById("cancel").hidden = !playing;
+    document.getElementById("play").disabled = playing;
+    document.getElementById("progress").textContent = describe_auto_solve(state);
+
+    if (playing) {
+        setTimeout(() => send_to_page("status").then(show_auto_solve), 500);
+    }
+}
+
+document.getElementById("solve").addEventListener("click", () => send_to_page("auto_solve").then(show_auto_solve));
+document.getElementById("cancel").addEventListener("click", () => send_to_page("cancel").then(show_auto_solve));
+
+let revealed = 0;
+let report = null;
+
+function show_next_hint() {
+    let list = document.getElementById("hints");
+    let item = document.createElement("li");
+    item.textContent = describe(report.hints[revealed]);
+    list.appendChild(item);
+    revealed += 1;
+
+    document.getElementById("more").disabled = revealed >= report.hints.length;
+}
+
+document.getElementById("more").addEventListener("click", show_next_hint);
+
+document.getElementById("play").addEventListener("click", async () => {
+    let step = await send_to_page("play_once");
+    if (step.reason) {
+        document.getElementById("error").textContent = step.reason;
+    } else {
+        window.close();
+    }
+});
+
+send_to_page("status").then(show_auto_solve);
+
+run_in_page(page_hints).then(result => {
+    report = result;
+    if (report.error) {
+        document.getElementById("error").textContent = report.error;
+        document.getElementById("more").disabled = true;
+    } else {
+        show_next_hint();
+    }
+});
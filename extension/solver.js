@@ -0,0 +1,12 @@
+// Runs worker.js for content.js, which can't start a worker from the extension on the game's page. content.js loads this page in a hidden frame and sends it one end of a MessageChannel; messages pass between that port and the worker untouched.
+
+window.addEventListener("message", event => {
+    let [port] = event.ports;
+    if (event.source !== window.parent || event.data !== "helple-connect" || !port) {
+        return;
+    }
+
+    let worker = new Worker("worker.js");
+    port.onmessage = ({ data }) => worker.postMessage(data);
+    worker.onmessage = ({ data }) => port.postMessage(data);
+});
@@ -0,0 +1,55 @@
+// Runs worker.js and turns its messages into promises.
+//
+//     let worker = new HelpleWorker("worker.js");
+//     let job = worker.suggest(state, rejected, 5, progress => console.log(progress));
+//     job.cancel();
+//     let report = await job.result;  // rejects with "cancelled" if cancelled
+//
+// Instead of a URL it also takes a MessagePort whose other end passes messages to and from a worker, see solver.js.
+
+class HelpleWorker {
+    constructor(target) {
+        this.worker = typeof target == "string" ? new Worker(target) : target;
+        this.next_id = 0;
+        this.jobs = new Map();
+        this.worker.onmessage = ({ data }) => this.receive(data);
+    }
+
+    suggest(state, rejected, count, on_progress) {
+        let id = this.next_id++;
+        let result = new Promise((resolve, reject) => {
+            this.jobs.set(id, { resolve, reject, on_progress });
+        });
+
+        this.worker.postMessage({ type: "suggest", id, state, rejected, count });
+        return {
+            result,
+            cancel: () => this.worker.postMessage({ type: "cancel", id }),
+        };
+    }
+
+    receive(message) {
+        let job = this.jobs.get(message.id);
+        if (!job) {
+            return;
+        }
+
+        switch (message.type) {
+            case "progress":
+                if (job.on_progress) {
+                    job.on_progress(message.progress);
+                }
+                return;
+            case "result":
+                job.resolve(message.report);
+                break;
+            case "cancelled":
+                job.reject("cancelled");
+                break;
+            case "error":
+                job.reject(message.error);
+                break;
+        }
+        this.jobs.delete(message.id);
+    }
+}
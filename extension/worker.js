@@ -0,0 +1,63 @@
+// Works out suggestions off the main thread, so slow strategies don't freeze the page.
+//
+// Messages in:
+//   {type: "suggest", id, state, rejected, count}  state is shaped like a `Session`, rejected is a list of words
+//   {type: "cancel", id}
+// Messages out:
+//   {type: "progress", id, progress}  progress from 0 to 1
+//   {type: "result", id, report}      report is a `session::Report`
+//   {type: "cancelled", id}
+//   {type: "error", id, error}
+//
+// Use `HelpleWorker` in worker-client.js rather than posting these by hand.
+
+importScripts("helple.js");
+
+const ready = wasm_bindgen({ module_or_path: "helple_bg.wasm" });
+
+// Guesses scored between checks for a cancel message.
+const BATCH = 500;
+
+let cancelled = new Set();
+
+// Gives queued messages, like a cancel, a chance to be handled.
+function yield_to_messages() {
+    return new Promise(resolve => setTimeout(resolve, 0));
+}
+
+async function suggest({ id, state, rejected, count }) {
+    await ready;
+
+    let suggester;
+    try {
+        suggester = new wasm_bindgen.Suggester(state, rejected || [], count || 1);
+    } catch (error) {
+        postMessage({ type: "error", id, error: String(error) });
+        return;
+    }
+
+    while (!suggester.step(BATCH)) {
+        postMessage({ type: "progress", id, progress: suggester.progress() });
+        await yield_to_messages();
+
+        if (cancelled.has(id)) {
+            cancelled.delete(id);
+            suggester.free();
+            postMessage({ type: "cancelled", id });
+            return;
+        }
+    }
+
+    postMessage({ type: "result", id, report: suggester.report() });
+}
+
+onmessage = ({ data }) => {
+    switch (data.type) {
+        case "suggest":
+            suggest(data);
+            break;
+        case "cancel":
+            cancelled.add(data.id);
+            break;
+    }
+};
@@ -0,0 +1,348 @@
+use crate::{evaluate_guess_into, LetterStatus, Puzzle};
+use std::cmp::Reverse;
+use std::collections::HashSet;
+use std::fmt;
+
+/// How one turn of a finished game played out, measured against the answers that were still possible at the time.
+#[derive(Debug, PartialEq)]
+pub struct TurnReport {
+    pub guess: String,
+    pub feedback: Vec<LetterStatus>,
+    pub candidates_before: usize,
+    pub candidates_after: usize,
+    /// Bits of information the player's guess was expected to reveal.
+    pub expected_bits: f64,
+    /// Bits of information the feedback actually revealed.
+    pub actual_bits: f64,
+    pub best_guess: String,
+    pub best_expected_bits: f64,
+    /// What `Puzzle::suggest_word` would have played at this point.
+    pub helple_guess: Option<String>,
+}
+
+impl TurnReport {
+    /// How close the guess came to the most informative one available, from 0 to 1.
+    pub fn skill(&self) -> f64 {
+        if self.best_expected_bits <= 0.0 {
+            // Only one candidate was left, so the only good play was to guess it.
+            return if self.candidates_after == 1 && self.feedback_is_solved() {
+                1.0
+            } else {
+                0.0
+            };
+        }
+
+        self.expected_bits / self.best_expected_bits
+    }
+
+    /// Bits revealed beyond what the guess was expected to reveal. Positive is lucky, negative unlucky.
+    pub fn luck(&self) -> f64 {
+        self.actual_bits - self.expected_bits
+    }
+
+    fn feedback_is_solved(&self) -> bool {
+        self.feedback.iter().all(|s| *s == LetterStatus::Correct)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct GameReport {
+    pub turns: Vec<TurnReport>,
+}
+
+impl GameReport {
+    /// Average skill across all turns.
+    pub fn skill(&self) -> f64 {
+        if self.turns.is_empty() {
+            return 0.0;
+        }
+
+        self.turns.iter().map(|t| t.skill()).sum::<f64>() / self.turns.len() as f64
+    }
+
+    /// Total luck across all turns.
+    pub fn luck(&self) -> f64 {
+        self.turns.iter().map(|t| t.luck()).sum()
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum AnalysisError {
+    /// The feedback on this turn (1-based) rules out every answer.
+    NoCandidates {
+        turn: usize,
+    },
+    LengthMismatch {
+        turn: usize,
+    },
+}
+
+impl fmt::Display for AnalysisError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AnalysisError::NoCandidates { turn } => {
+                write!(
+                    f,
+                    "No answer is consistent with the results up to guess {}",
+                    turn
+                )
+            }
+            AnalysisError::LengthMismatch { turn } => write!(
+                f,
+                "Guess {} and its results must have the same number of letters",
+                turn
+            ),
+        }
+    }
+}
+
+impl std::error::Error for AnalysisError {}
+
+/// Numbers each distinct feedback pattern so they can be counted in a flat array.
+fn pattern_index(feedback: &[LetterStatus]) -> usize {
+    feedback.iter().fold(0, |index, status| {
+        index * 3
+            + match status {
+                LetterStatus::NotInWord => 0,
+                LetterStatus::WrongSpot => 1,
+                LetterStatus::Correct => 2,
+            }
+    })
+}
+
+/// Shannon entropy, in bits, of the feedback `guess` would produce across `candidates`.
+pub fn expected_information(guess: &str, candidates: &[String]) -> f64 {
+    if candidates.is_empty() {
+        return 0.0;
+    }
+
+    let mut counts = vec![0usize; 3usize.pow(guess.len() as u32)];
+    let mut feedback: Vec<LetterStatus> = Vec::with_capacity(guess.len());
+
+    for candidate in candidates {
+        evaluate_guess_into(guess.as_bytes(), candidate.as_bytes(), &mut feedback);
+        counts[pattern_index(&feedback)] += 1;
+    }
+
+    let total = candidates.len() as f64;
+    counts
+        .iter()
+        .filter(|c| **c > 0)
+        .map(|c| {
+            let p = *c as f64 / total;
+            p * (1.0 / p).log2()
+        })
+        .sum()
+}
+
+/// Every word in `dictionary` with its expected information, most informative first. Ties go to words that could still be the answer, then to dictionary order.
+pub(crate) fn rank_guesses(candidates: &[String], dictionary: &[String]) -> Vec<(String, f64)> {
+    GuessRanking::new(candidates.to_vec(), dictionary.to_vec()).finish()
+}
+
+/// `rank_guesses` worked out a batch of guesses at a time, so that a long ranking can report its progress or be abandoned part way.
+pub struct GuessRanking {
+    candidates: Vec<String>,
+    guesses: Vec<String>,
+    scored: Vec<(String, f64, bool)>,
+}
+
+impl GuessRanking {
+    pub fn new(candidates: Vec<String>, guesses: Vec<String>) -> GuessRanking {
+        GuessRanking {
+            candidates,
+            scored: Vec::with_capacity(guesses.len()),
+            guesses,
+        }
+    }
+
+    /// Scores up to `batch` more guesses. Returns whether every guess has been scored.
+    pub fn step(&mut self, batch: usize) -> bool {
+        let possible: HashSet<&String> = self.candidates.iter().collect();
+        let start = self.scored.len();
+        let end = start.saturating_add(batch).min(self.guesses.len());
+
+        for word in &self.guesses[start..end] {
+            let bits = expected_information(word, &self.candidates);
+            self.scored
+                .push((word.clone(), bits, possible.contains(word)));
+        }
+
+        self.is_done()
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.scored.len() == self.guesses.len()
+    }
+
+    /// The share of guesses scored so far, from 0 to 1.
+    pub fn progress(&self) -> f64 {
+        if self.guesses.is_empty() {
+            return 1.0;
+        }
+
+        self.scored.len() as f64 / self.guesses.len() as f64
+    }
+
+    /// Scores whatever is left and returns the ranking.
+    pub fn finish(mut self) -> Vec<(String, f64)> {
+        self.step(usize::MAX);
+
+        // Round so that sums which only differ by floating point error compare as equal.
+        self.scored.sort_by_key(|(_, bits, is_candidate)| {
+            (Reverse((bits * 1e9).round() as i64), Reverse(*is_candidate))
+        });
+
+        self.scored
+            .into_iter()
+            .map(|(word, bits, _)| (word, bits))
+            .collect()
+    }
+}
+
+/// The most informative guess from `dictionary`, see `rank_guesses`.
+fn best_guess(candidates: &[String], dictionary: &[String]) -> (String, f64) {
+    rank_guesses(candidates, dictionary)
+        .into_iter()
+        .next()
+        .unwrap_or((String::new(), 0.0))
+}
+
+/// Replays the guesses of a finished game turn by turn, scoring each against the best one that was available from the puzzle's dictionary given the remaining `answers`. What helple would have played comes from a fresh copy of `game`, so with its settings and exclusions.
+pub fn analyze(game: &Puzzle, answers: &[String]) -> Result<GameReport, AnalysisError> {
+    let mut puzzle = game.clone();
+    puzzle.reset();
+    let dictionary = game.dictionary();
+    let mut candidates: Vec<String> = answers
+        .iter()
+        .filter(|answer| !game.excluded().contains(*answer))
+        .cloned()
+        .collect();
+    let mut turns: Vec<TurnReport> = Vec::new();
+    let mut feedback: Vec<LetterStatus> = Vec::new();
+
+    for (i, (guess, statuses)) in game.guesses().iter().enumerate() {
+        if guess.len() != statuses.len() {
+            return Err(AnalysisError::LengthMismatch { turn: i + 1 });
+        }
+
+        let helple_guess = puzzle.suggest_word(&HashSet::new());
+        let (best, best_expected_bits) = best_guess(&candidates, dictionary);
+        let expected_bits = expected_information(guess, &candidates);
+
+        let candidates_before = candidates.len();
+        candidates.retain(|answer| {
+            evaluate_guess_into(guess.as_bytes(), answer.as_bytes(), &mut feedback);
+            feedback == *statuses
+        });
+
+        if candidates.is_empty() {
+            return Err(AnalysisError::NoCandidates { turn: i + 1 });
+        }
+
+        turns.push(TurnReport {
+            guess: guess.clone(),
+            feedback: statuses.clone(),
+            candidates_before,
+            candidates_after: candidates.len(),
+            expected_bits,
+            actual_bits: (candidates_before as f64 / candidates.len() as f64).log2(),
+            best_guess: best,
+            best_expected_bits,
+            helple_guess,
+        });
+
+        puzzle.assign_guess_results(guess.clone(), statuses.clone());
+    }
+
+    Ok(GameReport { turns })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{evaluate_guess, Settings};
+
+    fn words(list: &[&str]) -> Vec<String> {
+        list.iter().map(|w| w.to_string()).collect()
+    }
+
+    #[test]
+    fn expected_information_of_a_perfect_split() {
+        let candidates = words(&["KNOLL", "TANGY", "ABBEY", "RUSTY"]);
+
+        // Each candidate gives a different pattern, so the guess identifies the answer outright.
+        assert_eq!(2.0, expected_information("TANGY", &candidates));
+        assert_eq!(0.0, expected_information("TANGY", &words(&["TANGY"])));
+    }
+
+    #[test]
+    fn ranks_in_batches() {
+        let candidates = words(&["KNOLL", "TANGY", "TANKY", "TONEY"]);
+        let dictionary = words(&["KNOLL", "TANGY", "TANKY", "TONEY", "RUSTY"]);
+
+        let mut ranking = GuessRanking::new(candidates.clone(), dictionary.clone());
+        assert!(!ranking.step(2));
+        assert_eq!(0.4, ranking.progress());
+        assert!(!ranking.step(2));
+        assert!(ranking.step(2));
+        assert_eq!(1.0, ranking.progress());
+
+        assert_eq!(rank_guesses(&candidates, &dictionary), ranking.finish());
+    }
+
+    #[test]
+    fn replays_game() {
+        let answers = words(&["KNOLL", "TANGY", "TANKY", "TONEY"]);
+        let dictionary = words(&["KNOLL", "TANGY", "TANKY", "TONEY", "RUSTY"]);
+        let mut game = Puzzle::with_settings(dictionary.clone(), Settings::default());
+        game.assign_guess_results("RUSTY".to_string(), evaluate_guess("RUSTY", "TANGY"));
+        game.assign_guess_results("TANGY".to_string(), evaluate_guess("TANGY", "TANGY"));
+
+        let report = analyze(&game, &answers).unwrap();
+        assert_eq!(2, report.turns.len());
+
+        let first = &report.turns[0];
+        assert_eq!(4, first.candidates_before);
+        assert_eq!(3, first.candidates_after);
+        assert_eq!(Some("RUSTY".to_string()), first.helple_guess);
+        assert!(first.best_expected_bits > first.expected_bits);
+        assert!(first.skill() < 1.0);
+
+        let last = &report.turns[1];
+        assert_eq!(3, last.candidates_before);
+        assert_eq!(1, last.candidates_after);
+        assert_eq!("TANGY", last.best_guess);
+        assert_eq!(1.0, last.skill());
+        assert!(last.luck().abs() < 1e-9);
+    }
+
+    #[test]
+    fn rejects_impossible_feedback() {
+        let answers = words(&["KNOLL", "TANGY"]);
+        let mut game = Puzzle::with_settings(answers.clone(), Settings::default());
+        game.assign_guess_results("RUSTY".to_string(), vec![LetterStatus::Correct; 5]);
+
+        assert_eq!(
+            Err(AnalysisError::NoCandidates { turn: 1 }),
+            analyze(&game, &answers)
+        );
+    }
+
+    #[test]
+    fn replays_with_the_games_settings() {
+        let answers = words(&["KNOLL", "TANGY", "TANKY", "TONEY"]);
+        let settings = Settings {
+            opening: Some("KNOLL".to_string()),
+            ..Settings::default()
+        };
+        let mut game = Puzzle::with_settings(answers.clone(), settings);
+        game.exclude_answers(vec!["TONEY".to_string()]);
+        game.assign_guess_results("TANKY".to_string(), evaluate_guess("TANKY", "TANGY"));
+
+        let report = analyze(&game, &answers).unwrap();
+        let first = &report.turns[0];
+        assert_eq!(Some("KNOLL".to_string()), first.helple_guess);
+        assert_eq!(3, first.candidates_before);
+    }
+}
@@ -0,0 +1,333 @@
+use crate::game::MAX_GUESSES;
+use crate::session::{Session, SessionGuess, SESSION_VERSION};
+use crate::Settings;
+use std::fmt;
+use std::str::FromStr;
+use wasm_bindgen::JsCast;
+use web_sys::{Document, DocumentFragment, Element, HtmlElement, NodeList};
+
+/// A key on the game's on-screen keyboard.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Key {
+    Letter(char),
+    Enter,
+    Backspace,
+}
+
+impl FromStr for Key {
+    type Err = BoardError;
+
+    /// A letter, `Enter` or `Backspace`, as JavaScript names them.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+
+        match (chars.next(), chars.next()) {
+            (Some(letter), None) if letter.is_ascii_alphabetic() => {
+                Ok(Key::Letter(letter.to_ascii_uppercase()))
+            }
+            _ if s == "Enter" => Ok(Key::Enter),
+            _ if s == "Backspace" => Ok(Key::Backspace),
+            _ => Err(BoardError::UnknownKey(s.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum BoardError {
+    /// Part of the page wasn't where we expected, usually because the game's markup changed.
+    Missing(String),
+    /// helple doesn't know how to play the game at this URL.
+    UnsupportedSite(String),
+    UnknownKey(String),
+    UnknownEvaluation {
+        row: usize,
+        evaluation: String,
+    },
+}
+
+impl fmt::Display for BoardError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BoardError::Missing(what) => write!(f, "Couldn't find {} on the page", what),
+            BoardError::UnsupportedSite(url) => {
+                write!(f, "helple doesn't know how to play {}", url)
+            }
+            BoardError::UnknownKey(key) => write!(f, "Unknown key <{}>", key),
+            BoardError::UnknownEvaluation { row, evaluation } => {
+                write!(f, "Unknown tile <{}> in row {}", evaluation, row)
+            }
+        }
+    }
+}
+
+impl std::error::Error for BoardError {}
+
+/// A row of the board: its word and its tiles' evaluations, `None` for tiles that haven't been revealed.
+pub type Row = (String, Vec<Option<String>>);
+
+/// Reads and plays the game board of whichever page helple is running in.
+pub trait BoardAdapter {
+    /// Every row of the board, submitted or not.
+    fn read_rows(&self) -> Result<Vec<Row>, BoardError>;
+
+    fn press_key(&self, key: Key) -> Result<(), BoardError>;
+
+    /// The guesses submitted so far, with their results.
+    fn read_state(&self) -> Result<Session, BoardError> {
+        session_from_rows(self.read_rows()?)
+    }
+
+    /// The word typed into the board but not submitted yet, if any.
+    fn typed_word(&self) -> Result<Option<String>, BoardError> {
+        Ok(typed_word(self.read_rows()?))
+    }
+
+    /// Guesses the game allows before it's lost.
+    fn max_guesses(&self) -> usize {
+        MAX_GUESSES
+    }
+
+    /// Types `word` and submits it.
+    fn play_word(&self, word: &str) -> Result<(), BoardError> {
+        for letter in word.chars() {
+            self.press_key(Key::Letter(letter))?;
+        }
+
+        self.press_key(Key::Enter)
+    }
+}
+
+/// The result character (`X`, `.` or `-`) for a tile's evaluation, as the original game names it. Site adapters translate their own markup to these names.
+pub fn evaluation_result(evaluation: &str) -> Option<char> {
+    match evaluation {
+        "correct" => Some('X'),
+        "present" => Some('.'),
+        "absent" => Some('-'),
+        _ => None,
+    }
+}
+
+/// Builds a session from the rows of a board, each given as its word and its tiles' evaluations. The first row that hasn't been submitted, with no word or with unevaluated tiles, ends the board.
+pub fn session_from_rows<I>(rows: I) -> Result<Session, BoardError>
+where
+    I: IntoIterator<Item = Row>,
+{
+    let mut guesses: Vec<SessionGuess> = Vec::new();
+
+    for (i, (word, evaluations)) in rows.into_iter().enumerate() {
+        if word.is_empty() || evaluations.iter().any(|e| e.is_none()) {
+            break;
+        }
+
+        let mut results = String::new();
+        for evaluation in evaluations.into_iter().flatten() {
+            match evaluation_result(&evaluation) {
+                Some(result) => results.push(result),
+                None => {
+                    return Err(BoardError::UnknownEvaluation {
+                        row: i + 1,
+                        evaluation,
+                    })
+                }
+            }
+        }
+
+        guesses.push(SessionGuess { word, results });
+    }
+
+    Ok(Session {
+        version: SESSION_VERSION,
+        settings: Settings::default(),
+        guesses,
+    })
+}
+
+/// The word in the first row that hasn't been submitted, if anything has been typed into it.
+pub fn typed_word<I>(rows: I) -> Option<String>
+where
+    I: IntoIterator<Item = Row>,
+{
+    rows.into_iter()
+        .find(|(word, evaluations)| word.is_empty() || evaluations.iter().any(|e| e.is_none()))
+        .map(|(word, _)| word)
+        .filter(|word| !word.is_empty())
+}
+
+/// Something `querySelector` can be called on: the document, a shadow root or an element.
+pub(crate) trait Query {
+    fn query(&self, selector: &str) -> Option<Element>;
+    fn query_all(&self, selector: &str) -> Option<NodeList>;
+
+    fn select(&self, selector: &str) -> Result<Element, BoardError> {
+        self.query(selector)
+            .ok_or_else(|| BoardError::Missing(selector.to_string()))
+    }
+
+    /// Every element matching `selector`, in document order.
+    fn select_all(&self, selector: &str) -> Vec<Element> {
+        let nodes = match self.query_all(selector) {
+            Some(nodes) => nodes,
+            None => return vec![],
+        };
+
+        (0..nodes.length())
+            .filter_map(|i| nodes.get(i))
+            .filter_map(|node| node.dyn_into::<Element>().ok())
+            .collect()
+    }
+}
+
+macro_rules! impl_query {
+    ($($t:ty),*) => {
+        $(impl Query for $t {
+            fn query(&self, selector: &str) -> Option<Element> {
+                self.query_selector(selector).ok().flatten()
+            }
+
+            fn query_all(&self, selector: &str) -> Option<NodeList> {
+                self.query_selector_all(selector).ok()
+            }
+        })*
+    };
+}
+
+impl_query!(Document, DocumentFragment, Element);
+
+pub(crate) fn document() -> Result<Document, BoardError> {
+    web_sys::window()
+        .and_then(|window| window.document())
+        .ok_or_else(|| BoardError::Missing("the document".to_string()))
+}
+
+pub(crate) fn shadow_root(element: &Element, name: &str) -> Result<DocumentFragment, BoardError> {
+    element
+        .shadow_root()
+        .map(|root| root.into())
+        .ok_or_else(|| BoardError::Missing(format!("{}'s shadow root", name)))
+}
+
+/// Clicks a key on the page's keyboard.
+pub(crate) fn click(element: Element, name: &str) -> Result<(), BoardError> {
+    match element.dyn_into::<HtmlElement>() {
+        Ok(element) => {
+            element.click();
+            Ok(())
+        }
+        Err(_) => Err(BoardError::Missing(format!("a clickable {} key", name))),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::cell::RefCell;
+
+    /// A board held in memory, which records the keys pressed on it.
+    struct FakeBoard {
+        rows: Vec<Row>,
+        pressed: RefCell<Vec<Key>>,
+    }
+
+    impl BoardAdapter for FakeBoard {
+        fn read_rows(&self) -> Result<Vec<Row>, BoardError> {
+            Ok(self.rows.clone())
+        }
+
+        fn press_key(&self, key: Key) -> Result<(), BoardError> {
+            self.pressed.borrow_mut().push(key);
+            Ok(())
+        }
+    }
+
+    fn row(word: &str, evaluations: &[&str]) -> Row {
+        let evaluations = evaluations
+            .iter()
+            .map(|e| match *e {
+                "" => None,
+                e => Some(e.to_string()),
+            })
+            .collect();
+
+        (word.to_string(), evaluations)
+    }
+
+    #[test]
+    fn reads_submitted_rows() {
+        let board = FakeBoard {
+            rows: vec![
+                row(
+                    "RUSTY",
+                    &["absent", "absent", "absent", "present", "correct"],
+                ),
+                // Typed but not submitted yet.
+                row("TONEY", &["", "", "", "", ""]),
+                row("", &["", "", "", "", ""]),
+            ],
+            pressed: RefCell::new(vec![]),
+        };
+
+        let session = board.read_state().unwrap();
+        assert_eq!(1, session.guesses.len());
+        assert_eq!("---.X", session.guesses[0].results);
+        assert_eq!(Ok(Some("TONEY".to_string())), board.typed_word());
+
+        let board = FakeBoard {
+            rows: vec![row(
+                "RUSTY",
+                &["absent", "absent", "absent", "tbd", "correct"],
+            )],
+            pressed: RefCell::new(vec![]),
+        };
+        assert_eq!(
+            Err(BoardError::UnknownEvaluation {
+                row: 1,
+                evaluation: "tbd".to_string()
+            }),
+            board.read_state()
+        );
+    }
+
+    #[test]
+    fn finds_nothing_typed_on_a_finished_or_empty_row() {
+        let submitted = row(
+            "RUSTY",
+            &["absent", "absent", "absent", "present", "correct"],
+        );
+
+        assert_eq!(None, typed_word(vec![submitted.clone()]));
+        assert_eq!(
+            None,
+            typed_word(vec![submitted, row("", &["", "", "", "", ""])])
+        );
+    }
+
+    #[test]
+    fn plays_words_key_by_key() {
+        let board = FakeBoard {
+            rows: vec![],
+            pressed: RefCell::new(vec![]),
+        };
+        board.play_word("ABC").unwrap();
+
+        assert_eq!(
+            vec![
+                Key::Letter('A'),
+                Key::Letter('B'),
+                Key::Letter('C'),
+                Key::Enter
+            ],
+            *board.pressed.borrow()
+        );
+    }
+
+    #[test]
+    fn parses_key_names() {
+        assert_eq!(Ok(Key::Letter('Q')), "q".parse());
+        assert_eq!(Ok(Key::Enter), "Enter".parse());
+        assert_eq!(Ok(Key::Backspace), "Backspace".parse());
+        assert_eq!(
+            Err(BoardError::UnknownKey("Tab".to_string())),
+            "Tab".parse::<Key>()
+        );
+    }
+}
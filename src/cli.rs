@@ -0,0 +1,314 @@
+use helple::daily::Date;
+use helple::session::Session;
+use helple::{parse_feedback, Settings, Strategy};
+
+pub const USAGE: &str = r#"Usage: helple [COMMAND] [OPTIONS] [ARGS]
+
+Commands:
+  solve                        Suggest guesses interactively (default)
+  tui [WORD:RESULTS]...        Solve on a full-screen board, cycling tile colours to enter results
+  suggest [WORD:RESULTS]...    Print the next suggestion (or --top N) for the guesses so far
+  filter [WORD:RESULTS]...     List the words that are still possible answers
+  simulate [ANSWER]...         Play helple's suggestions against answers (all answers when none given)
+  explain WORD [WORD:RESULTS]...
+                               Explain why WORD is or isn't still a possible answer
+  analyze [WORD:RESULTS]...    Score each guess of a finished game for skill and luck
+  share                        Read a shared emoji grid from stdin and list the answers it could be
+  wordlist                     Print the word list in use
+  play                         Guess a hidden answer, picked at random, by --seed or by --date
+
+Options:
+  --words FILE                 Guess from the words in FILE, one per line
+  --strategy heuristic|entropy How to pick the next guess (default: heuristic)
+  --hard                       Only suggest words that use every hint revealed so far
+  --length N                   Number of letters per word (default: 5)
+  --opening WORD               First word to play (default: RUSTY)
+  --top N                      Number of suggestions to print (default: 1)
+  --exclude WORD,...           Rule these words out as answers, e.g. ones that have come up before
+  --history FILE               Rule out the answers listed in FILE, and add each answer solved to it
+  --load FILE                  Resume a saved session, including settings not given here
+  --save FILE                  Save the session as JSON once the guesses are in
+  --json                       Print results as JSON
+  --seed N                     Play the game for seed N
+  --date YYYY-MM-DD|today      The daily puzzle for that day: play it, or rule out earlier answers when solving it
+  -h, --help                   Print this message
+
+Results are typed as `X` or `x` (right letter, right position), `.` (right letter, wrong position) and `-` (letter not in word), e.g. `RUSTY:---.X`.
+Results on their own, e.g. `---.X`, are for the word helple would have suggested at that point."#;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Command {
+    Solve,
+    Tui,
+    Suggest,
+    Filter,
+    Simulate,
+    Explain,
+    Analyze,
+    Share,
+    Wordlist,
+    Play,
+    Help,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Options {
+    pub command: Command,
+    /// Positional arguments following the command.
+    pub args: Vec<String>,
+    pub words: Option<String>,
+    pub top: usize,
+    pub json: bool,
+    pub seed: Option<u64>,
+    pub date: Option<Date>,
+    pub exclude: Vec<String>,
+    pub history: Option<String>,
+    pub load: Option<String>,
+    pub save: Option<String>,
+    /// The session read from `load`, see `Options::resume`.
+    pub session: Option<Session>,
+    pub settings: Settings,
+    /// Which of `settings` were given on the command line.
+    pub given: SettingsGiven,
+}
+
+/// Settings options that were given on the command line, so they win over a loaded session's.
+#[derive(Debug, Default, PartialEq)]
+pub struct SettingsGiven {
+    pub strategy: bool,
+    pub hard_mode: bool,
+    pub word_length: bool,
+    pub opening: bool,
+}
+
+impl Options {
+    /// Resumes `session` with its settings, except those given on the command line. A `--length` that doesn't match the session's guesses is an error.
+    pub fn resume(&mut self, mut session: Session) -> Result<(), String> {
+        let mut settings = session.settings.clone();
+
+        if self.given.strategy {
+            settings.strategy = self.settings.strategy;
+        }
+        if self.given.hard_mode {
+            settings.hard_mode = self.settings.hard_mode;
+        }
+        if self.given.opening {
+            settings.opening = self.settings.opening.clone();
+        }
+        if self.given.word_length {
+            if let Some(guess) = session
+                .guesses
+                .iter()
+                .find(|guess| guess.word.len() != self.settings.word_length)
+            {
+                return Err(format!(
+                    "--length {} doesn't fit the saved guess <{}>",
+                    self.settings.word_length, guess.word
+                ));
+            }
+            settings.word_length = self.settings.word_length;
+        }
+
+        session.settings = settings.clone();
+        self.settings = settings;
+        self.session = Some(session);
+        Ok(())
+    }
+}
+
+fn command(name: &str) -> Option<Command> {
+    match name {
+        "solve" => Some(Command::Solve),
+        "tui" => Some(Command::Tui),
+        "suggest" => Some(Command::Suggest),
+        "filter" => Some(Command::Filter),
+        "simulate" => Some(Command::Simulate),
+        "explain" => Some(Command::Explain),
+        "analyze" => Some(Command::Analyze),
+        "share" => Some(Command::Share),
+        "wordlist" => Some(Command::Wordlist),
+        "play" => Some(Command::Play),
+        "help" => Some(Command::Help),
+        _ => None,
+    }
+}
+
+/// Parses command line arguments, excluding the program name. Options may appear anywhere after the command.
+pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Options, String> {
+    let mut args = args.into_iter();
+    let mut options = Options {
+        command: Command::Solve,
+        args: vec![],
+        words: None,
+        top: 1,
+        json: false,
+        seed: None,
+        date: None,
+        exclude: vec![],
+        history: None,
+        load: None,
+        save: None,
+        session: None,
+        settings: Settings::default(),
+        given: SettingsGiven::default(),
+    };
+    let mut command_found = false;
+
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| {
+            args.next()
+                .ok_or_else(|| format!("Missing value for {}", name))
+        };
+
+        match arg.as_str() {
+            "-h" | "--help" => options.command = Command::Help,
+            "--hard" => {
+                options.settings.hard_mode = true;
+                options.given.hard_mode = true;
+            }
+            "--json" => options.json = true,
+            "--words" => options.words = Some(value("--words")?),
+            "--strategy" => {
+                options.settings.strategy = value("--strategy")?.parse::<Strategy>()?;
+                options.given.strategy = true;
+            }
+            "--length" => {
+                let length = value("--length")?;
+                options.settings.word_length = match length.parse() {
+                    Ok(length) if length > 0 => length,
+                    _ => return Err(format!("Invalid word length <{}>", length)),
+                };
+                options.given.word_length = true;
+            }
+            "--top" => {
+                let top = value("--top")?;
+                options.top = match top.parse() {
+                    Ok(top) if top > 0 => top,
+                    _ => return Err(format!("Invalid number of suggestions <{}>", top)),
+                };
+            }
+            "--seed" => {
+                let seed = value("--seed")?;
+                options.seed = match seed.parse() {
+                    Ok(seed) => Some(seed),
+                    Err(_) => return Err(format!("Invalid seed <{}>", seed)),
+                };
+            }
+            "--date" => options.date = Some(value("--date")?.parse()?),
+            "--exclude" => options.exclude.extend(
+                value("--exclude")?
+                    .split(',')
+                    .filter(|word| !word.trim().is_empty())
+                    .map(|word| word.trim().to_uppercase()),
+            ),
+            "--history" => options.history = Some(value("--history")?),
+            "--load" => options.load = Some(value("--load")?),
+            "--save" => options.save = Some(value("--save")?),
+            "--opening" => {
+                options.settings.opening = Some(value("--opening")?.to_uppercase());
+                options.given.opening = true;
+            }
+            // Results such as `---.X` or `--XXX` also start with dashes.
+            flag if flag.starts_with("--") && parse_feedback(&flag.to_uppercase()).is_err() => {
+                return Err(format!("Unknown option {}", flag))
+            }
+            _ if !command_found && options.args.is_empty() => match command(&arg) {
+                Some(command) => {
+                    command_found = true;
+                    if options.command != Command::Help {
+                        options.command = command;
+                    }
+                }
+                None => return Err(format!("Unknown command <{}>", arg)),
+            },
+            _ => options.args.push(arg),
+        }
+    }
+
+    Ok(options)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|a| a.to_string()).collect()
+    }
+
+    #[test]
+    fn defaults_to_solve() {
+        let options = parse(args(&[])).unwrap();
+
+        assert_eq!(Command::Solve, options.command);
+        assert_eq!(Settings::default(), options.settings);
+    }
+
+    #[test]
+    fn parses_command_options_and_args() {
+        let options = parse(args(&[
+            "suggest",
+            "--strategy",
+            "entropy",
+            "RUSTY:---.X",
+            "--hard",
+            "TONEY:X-X-X",
+        ]))
+        .unwrap();
+
+        assert_eq!(Command::Suggest, options.command);
+        assert_eq!(args(&["RUSTY:---.X", "TONEY:X-X-X"]), options.args);
+        assert_eq!(Strategy::Entropy, options.settings.strategy);
+        assert!(options.settings.hard_mode);
+    }
+
+    #[test]
+    fn bare_results_are_not_options() {
+        let options = parse(args(&["suggest", "---.X", "--top", "3", "X-X-X"])).unwrap();
+
+        assert_eq!(args(&["---.X", "X-X-X"]), options.args);
+        assert_eq!(3, options.top);
+
+        let options = parse(args(&[
+            "filter",
+            "--exclude",
+            "tangy, knoll",
+            "--exclude",
+            "abbey",
+        ]))
+        .unwrap();
+        assert_eq!(args(&["TANGY", "KNOLL", "ABBEY"]), options.exclude);
+        assert!(parse(args(&["suggest", "--fast"])).is_err());
+
+        let options = parse(args(&["suggest", "--XXX", "--xx."])).unwrap();
+        assert_eq!(args(&["--XXX", "--xx."]), options.args);
+    }
+
+    #[test]
+    fn options_override_a_loaded_session() {
+        let session = || {
+            Session::from_json(
+                r#"{"version": 1, "settings": {"hard_mode": true, "strategy": "entropy", "word_length": 5, "opening": "CRANE"}, "guesses": [{"word": "RUSTY", "results": "---.X"}]}"#,
+            )
+            .unwrap()
+        };
+
+        let mut options = parse(args(&["suggest", "--strategy", "heuristic"])).unwrap();
+        options.resume(session()).unwrap();
+        assert_eq!(Strategy::Heuristic, options.settings.strategy);
+        assert!(options.settings.hard_mode);
+        assert_eq!(Some("CRANE".to_string()), options.settings.opening);
+        assert_eq!(options.settings, options.session.as_ref().unwrap().settings);
+
+        let mut options = parse(args(&["suggest", "--length", "6"])).unwrap();
+        assert!(options.resume(session()).is_err());
+    }
+
+    #[test]
+    fn rejects_bad_input() {
+        assert!(parse(args(&["frobnicate"])).is_err());
+        assert!(parse(args(&["suggest", "--length"])).is_err());
+        assert!(parse(args(&["suggest", "--length", "zero"])).is_err());
+        assert!(parse(args(&["suggest", "--strategy", "luck"])).is_err());
+    }
+}
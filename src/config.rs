@@ -0,0 +1,135 @@
+use crate::{Settings, Strategy};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// The longest pause between key presses the extension allows, in milliseconds.
+pub const MAX_TYPING_DELAY: u32 = 2000;
+
+/// The extension's options, as saved in extension storage. Fields missing from storage fall back to their defaults.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExtensionConfig {
+    /// Milliseconds between key presses when typing a guess.
+    pub typing_delay: u32,
+    /// Press Enter after typing a guess. When off, the guess is left for you to submit.
+    pub auto_submit: bool,
+    /// Show the panel of suggestions and remaining words on the game page.
+    pub show_overlay: bool,
+    pub hard_mode: bool,
+    pub strategy: Strategy,
+    pub opening: Option<String>,
+}
+
+impl Default for ExtensionConfig {
+    fn default() -> Self {
+        let settings = Settings::default();
+
+        ExtensionConfig {
+            typing_delay: 200,
+            auto_submit: true,
+            show_overlay: true,
+            hard_mode: settings.hard_mode,
+            strategy: settings.strategy,
+            opening: settings.opening,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ConfigError {
+    TypingDelay(u32),
+    /// The opening word isn't one helple could play.
+    Opening(String),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConfigError::TypingDelay(delay) => write!(
+                f,
+                "Typing delay must be at most {}ms, not {}ms",
+                MAX_TYPING_DELAY, delay
+            ),
+            ConfigError::Opening(word) => write!(f, "<{}> isn't in the word list", word),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl ExtensionConfig {
+    /// Checks the options against `dictionary`, uppercasing the opening word and treating a blank one as unset.
+    pub fn validate(mut self, dictionary: &[String]) -> Result<ExtensionConfig, ConfigError> {
+        if self.typing_delay > MAX_TYPING_DELAY {
+            return Err(ConfigError::TypingDelay(self.typing_delay));
+        }
+
+        self.opening = match self.opening.map(|word| word.trim().to_uppercase()) {
+            Some(word) if word.is_empty() => None,
+            Some(word) if !dictionary.contains(&word) => return Err(ConfigError::Opening(word)),
+            opening => opening,
+        };
+
+        Ok(self)
+    }
+
+    /// The solver settings these options choose.
+    pub fn settings(&self) -> Settings {
+        Settings {
+            hard_mode: self.hard_mode,
+            strategy: self.strategy,
+            opening: self.opening.clone(),
+            ..Settings::default()
+        }
+    }
+}
+
+/// A validated config, or why it was rejected, as returned to JavaScript.
+#[derive(Debug, Default, PartialEq, Serialize)]
+pub struct ConfigReport {
+    pub config: Option<ExtensionConfig>,
+    pub error: Option<String>,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::words;
+
+    #[test]
+    fn validates_options() {
+        let dictionary = words::dictionary();
+
+        let config: ExtensionConfig =
+            serde_json::from_str(r#"{"strategy": "entropy", "opening": " crane "}"#).unwrap();
+        let config = config.validate(&dictionary).unwrap();
+        assert_eq!(Some("CRANE".to_string()), config.opening);
+        assert_eq!(200, config.typing_delay);
+        assert!(config.show_overlay);
+        assert_eq!(Strategy::Entropy, config.settings().strategy);
+
+        let blank = ExtensionConfig {
+            opening: Some("".to_string()),
+            ..ExtensionConfig::default()
+        };
+        assert_eq!(None, blank.validate(&dictionary).unwrap().opening);
+
+        let slow = ExtensionConfig {
+            typing_delay: 5000,
+            ..ExtensionConfig::default()
+        };
+        assert_eq!(
+            Err(ConfigError::TypingDelay(5000)),
+            slow.validate(&dictionary)
+        );
+
+        let made_up = ExtensionConfig {
+            opening: Some("qwxyz".to_string()),
+            ..ExtensionConfig::default()
+        };
+        assert_eq!(
+            Err(ConfigError::Opening("QWXYZ".to_string())),
+            made_up.validate(&dictionary)
+        );
+    }
+}
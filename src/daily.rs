@@ -0,0 +1,191 @@
+use std::fmt;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A calendar day, as used to schedule the daily puzzle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Date {
+    pub year: i32,
+    pub month: u32,
+    pub day: u32,
+}
+
+/// The day of the first puzzle, Wordle 0 ("cigar").
+pub const FIRST_PUZZLE: Date = Date {
+    year: 2021,
+    month: 6,
+    day: 19,
+};
+
+impl Date {
+    /// Today's date in UTC. The game itself goes by the player's local date, so this can be a day out around midnight.
+    pub fn today() -> Date {
+        let seconds = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs());
+
+        Date::from_days_since_epoch((seconds / 86400) as i64)
+    }
+
+    /// The inverse of `days_since_epoch`.
+    pub fn from_days_since_epoch(days: i64) -> Date {
+        // Howard Hinnant's civil_from_days.
+        let days = days + 719468;
+        let era = days.div_euclid(146097);
+        let day_of_era = days - era * 146097;
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let month_index = (5 * day_of_year + 2) / 153;
+        let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+        let month = if month_index < 10 {
+            month_index + 3
+        } else {
+            month_index - 9
+        } as u32;
+        let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+        Date {
+            year: year as i32,
+            month,
+            day,
+        }
+    }
+
+    /// Days since 1970-01-01, negative before it.
+    pub fn days_since_epoch(&self) -> i64 {
+        // Howard Hinnant's days_from_civil, counting years from March so the leap day comes last.
+        let year = if self.month <= 2 {
+            self.year as i64 - 1
+        } else {
+            self.year as i64
+        };
+        let era = year.div_euclid(400);
+        let year_of_era = year - era * 400;
+        let month = self.month as i64;
+        let day_of_year =
+            (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + self.day as i64 - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+        era * 146097 + day_of_era - 719468
+    }
+
+    fn days_in_month(year: i32, month: u32) -> u32 {
+        match month {
+            4 | 6 | 9 | 11 => 30,
+            2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+            2 => 28,
+            _ => 31,
+        }
+    }
+}
+
+impl FromStr for Date {
+    type Err = String;
+
+    /// Reads an ISO 8601 date, e.g. `2022-01-14`, or `today`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "today" {
+            return Ok(Date::today());
+        }
+
+        let invalid = || format!("Invalid date <{}>, expected YYYY-MM-DD", s);
+
+        let parts: Vec<&str> = s.split('-').collect();
+        let (year, month, day) = match parts.as_slice() {
+            [year, month, day] => (
+                year.parse().map_err(|_| invalid())?,
+                month.parse().map_err(|_| invalid())?,
+                day.parse().map_err(|_| invalid())?,
+            ),
+            _ => return Err(invalid()),
+        };
+
+        if !(1..=12).contains(&month) || day < 1 || day > Date::days_in_month(year, month) {
+            return Err(invalid());
+        }
+
+        Ok(Date { year, month, day })
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+/// The number of the puzzle scheduled for `date`, or `None` before the first puzzle.
+pub fn puzzle_number(date: Date) -> Option<u32> {
+    let days = date.days_since_epoch() - FIRST_PUZZLE.days_since_epoch();
+    u32::try_from(days).ok()
+}
+
+/// The day puzzle `number` was scheduled for.
+pub fn date_for_puzzle(number: u32) -> Date {
+    Date::from_days_since_epoch(FIRST_PUZZLE.days_since_epoch() + number as i64)
+}
+
+/// The answers scheduled before `date`, which won't come up again. Unlike `scheduled_answer` this never gives away the answer for `date` itself, so it is safe to use while solving that day's puzzle.
+pub fn past_answers(date: Date, answers: &[String]) -> &[String] {
+    let number = puzzle_number(date).map_or(0, |number| number as usize);
+    &answers[..number.min(answers.len())]
+}
+
+/// The answer scheduled for `date` from `answers`, in their original order. `None` before the first puzzle or once the list runs out.
+pub fn scheduled_answer(date: Date, answers: &[String]) -> Option<&str> {
+    let number = puzzle_number(date)? as usize;
+    answers.get(number).map(|answer| answer.as_str())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::words;
+
+    #[test]
+    fn parses_and_counts_days() {
+        let date: Date = "2022-01-14".parse().unwrap();
+
+        assert_eq!("2022-01-14", date.to_string());
+        assert_eq!(0, "1970-01-01".parse::<Date>().unwrap().days_since_epoch());
+        assert_eq!(19006, date.days_since_epoch());
+        assert!("2022-02-29".parse::<Date>().is_err());
+        assert!("2024-02-29".parse::<Date>().is_ok());
+        assert!("14/01/2022".parse::<Date>().is_err());
+    }
+
+    #[test]
+    fn schedules_answers_by_date() {
+        let answers = words::answers();
+
+        assert_eq!(Some("CIGAR"), scheduled_answer(FIRST_PUZZLE, &answers));
+        assert_eq!(Some(209), puzzle_number("2022-01-14".parse().unwrap()));
+        assert_eq!(
+            Some("TANGY"),
+            scheduled_answer("2022-01-14".parse().unwrap(), &answers)
+        );
+        assert_eq!(None, puzzle_number("2021-06-18".parse().unwrap()));
+        assert_eq!("2022-01-15", date_for_puzzle(210).to_string());
+    }
+
+    #[test]
+    fn converts_days_back_to_dates() {
+        for days in [-1, 0, 59, 10956, 11016, 19006, 19052] {
+            let date = Date::from_days_since_epoch(days);
+            assert_eq!(days, date.days_since_epoch(), "{}", date);
+        }
+        assert_eq!("2000-02-29", Date::from_days_since_epoch(11016).to_string());
+    }
+
+    #[test]
+    fn past_answers_stop_before_the_day() {
+        let answers = words::answers();
+        let past = past_answers("2022-01-14".parse().unwrap(), &answers);
+
+        assert_eq!(209, past.len());
+        assert_eq!("ABBEY", past.last().unwrap());
+        assert!(!past.contains(&"TANGY".to_string()));
+        assert!(past_answers(FIRST_PUZZLE, &answers).is_empty());
+    }
+}
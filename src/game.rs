@@ -0,0 +1,221 @@
+use crate::share::SharedGame;
+use crate::{evaluate_guess, LetterStatus};
+use std::collections::HashSet;
+use std::fmt;
+
+/// Guesses allowed per game.
+pub const MAX_GUESSES: usize = 6;
+
+/// A game of Wordle against a hidden answer, scored with the same rules as the real thing.
+pub struct Game {
+    answer: String,
+    allowed: HashSet<String>,
+    hard_mode: bool,
+    guesses: Vec<(String, Vec<LetterStatus>)>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum GameError {
+    Length {
+        expected: usize,
+    },
+    NotInWordList(String),
+    /// A hard mode rule the guess broke, e.g. `2nd letter must be R`.
+    HardMode(String),
+    Over,
+}
+
+impl fmt::Display for GameError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GameError::Length { expected } => write!(f, "Guesses must have {} letters", expected),
+            GameError::NotInWordList(word) => write!(f, "<{}> isn't in the word list", word),
+            GameError::HardMode(rule) => write!(f, "{}", rule),
+            GameError::Over => write!(f, "The game is over"),
+        }
+    }
+}
+
+impl std::error::Error for GameError {}
+
+fn ordinal(n: usize) -> String {
+    let suffix = match (n % 10, n % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+
+    format!("{}{}", n, suffix)
+}
+
+/// Picks an answer from `answers` for `seed`, so the same seed always plays the same game.
+pub fn answer_for_seed(seed: u64, answers: &[String]) -> Option<&str> {
+    if answers.is_empty() {
+        return None;
+    }
+
+    // splitmix64, so that neighbouring seeds don't give neighbouring answers.
+    let mut z = seed.wrapping_add(0x9e3779b97f4a7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^= z >> 31;
+
+    Some(&answers[(z % answers.len() as u64) as usize])
+}
+
+impl Game {
+    /// Starts a game. Guesses must come from `allowed`; the answer is always allowed.
+    pub fn new(answer: &str, allowed: &[String], hard_mode: bool) -> Game {
+        let answer = answer.to_uppercase();
+        let mut allowed: HashSet<String> = allowed.iter().map(|w| w.to_uppercase()).collect();
+        allowed.insert(answer.clone());
+
+        Game {
+            answer,
+            allowed,
+            hard_mode,
+            guesses: vec![],
+        }
+    }
+
+    pub fn answer(&self) -> &str {
+        &self.answer
+    }
+
+    pub fn guesses(&self) -> &[(String, Vec<LetterStatus>)] {
+        &self.guesses
+    }
+
+    pub fn is_won(&self) -> bool {
+        self.guesses
+            .last()
+            .is_some_and(|(word, _)| *word == self.answer)
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.is_won() || self.guesses.len() >= MAX_GUESSES
+    }
+
+    /// Plays `word`, returning its feedback.
+    pub fn guess(&mut self, word: &str) -> Result<&[LetterStatus], GameError> {
+        if self.is_over() {
+            return Err(GameError::Over);
+        }
+
+        let word = word.trim().to_uppercase();
+        if word.len() != self.answer.len() {
+            return Err(GameError::Length {
+                expected: self.answer.len(),
+            });
+        }
+        if !self.allowed.contains(&word) {
+            return Err(GameError::NotInWordList(word));
+        }
+        if self.hard_mode {
+            self.check_hard_mode(&word)?;
+        }
+
+        let statuses = evaluate_guess(&word, &self.answer);
+        self.guesses.push((word, statuses));
+        Ok(&self.guesses.last().unwrap().1)
+    }
+
+    /// Hard mode as the original game plays it: green letters stay put and yellow letters must be reused. Grey letters may be played again.
+    fn check_hard_mode(&self, word: &str) -> Result<(), GameError> {
+        let letters = word.as_bytes();
+
+        for (previous, statuses) in &self.guesses {
+            for (i, (letter, status)) in previous.bytes().zip(statuses).enumerate() {
+                if *status == LetterStatus::Correct && letters[i] != letter {
+                    return Err(GameError::HardMode(format!(
+                        "{} letter must be {}",
+                        ordinal(i + 1),
+                        letter as char
+                    )));
+                }
+            }
+
+            for (letter, status) in previous.bytes().zip(statuses) {
+                if *status == LetterStatus::WrongSpot && !letters.contains(&letter) {
+                    return Err(GameError::HardMode(format!(
+                        "Guess must contain {}",
+                        letter as char
+                    )));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The game as the share button would present it.
+    pub fn shared(&self, puzzle_number: Option<u32>) -> SharedGame {
+        SharedGame {
+            puzzle_number,
+            solved_in: if self.is_won() {
+                Some(self.guesses.len())
+            } else {
+                None
+            },
+            hard_mode: self.hard_mode,
+            rows: self.guesses.iter().map(|(_, row)| row.clone()).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{format_feedback, words};
+
+    #[test]
+    fn plays_to_a_win() {
+        let mut game = Game::new("tangy", &words::dictionary(), false);
+
+        assert_eq!("---.X", format_feedback(game.guess("rusty").unwrap()));
+        assert_eq!(
+            Err(GameError::NotInWordList("QWXYZ".to_string())),
+            game.guess("qwxyz").map(|_| ())
+        );
+        assert_eq!(
+            Err(GameError::Length { expected: 5 }),
+            game.guess("tan").map(|_| ())
+        );
+        game.guess("tangy").unwrap();
+
+        assert!(game.is_won());
+        assert_eq!(Err(GameError::Over), game.guess("abbey").map(|_| ()));
+        assert_eq!(
+            "Wordle 209 2/6\n\n⬜⬜⬜🟨🟩\n🟩🟩🟩🟩🟩",
+            game.shared(Some(209)).to_string()
+        );
+    }
+
+    #[test]
+    fn enforces_hard_mode() {
+        let mut game = Game::new("tangy", &words::dictionary(), true);
+        game.guess("tonal").unwrap();
+
+        assert_eq!(
+            Err(GameError::HardMode("1st letter must be T".to_string())),
+            game.guess("knoll").map(|_| ())
+        );
+        assert_eq!(
+            Err(GameError::HardMode("Guess must contain A".to_string())),
+            game.guess("tenth").map(|_| ())
+        );
+        // Grey letters can be played again.
+        assert!(game.guess("tansy").is_ok());
+    }
+
+    #[test]
+    fn seeds_pick_the_same_answer() {
+        let answers = words::answers();
+
+        assert_eq!(answer_for_seed(42, &answers), answer_for_seed(42, &answers));
+        assert_ne!(answer_for_seed(42, &answers), answer_for_seed(43, &answers));
+        assert_eq!(None, answer_for_seed(42, &[]));
+    }
+}
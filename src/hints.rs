@@ -0,0 +1,177 @@
+use crate::{LetterStatus, Puzzle};
+use serde::Serialize;
+use std::collections::HashSet;
+
+/// One step of help, from a nudge to the full answer. `Puzzle::hints` gives them in that order.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Hint {
+    CandidateCount {
+        count: usize,
+    },
+    /// A letter not yet found that must be in the answer, being in every remaining candidate. When no letter is, the one in the most candidates, which is only likely and not `certain`.
+    Letter {
+        letter: char,
+        candidates: usize,
+        certain: bool,
+    },
+    /// A letter and 1-based position not yet found, shared by the most remaining candidates. Only `certain` when every candidate has it.
+    Position {
+        letter: char,
+        position: usize,
+        candidates: usize,
+        certain: bool,
+    },
+    Word {
+        word: String,
+    },
+}
+
+/// The hints for a puzzle state, as returned to JavaScript. `error` is set instead when the state can't be read.
+#[derive(Debug, Default, PartialEq, Serialize)]
+pub struct HintReport {
+    pub hints: Vec<Hint>,
+    pub error: Option<String>,
+}
+
+impl Puzzle {
+    /// Hints for the next guess, each giving away more than the last: how many words are left, a letter, a letter in place, then the suggestion itself. Stops early once there is nothing left to reveal.
+    pub fn hints(&self) -> Vec<Hint> {
+        let candidates = self.candidates();
+        let mut hints = vec![Hint::CandidateCount {
+            count: candidates.len(),
+        }];
+
+        if let Some(hint) = letter_hint(self, &candidates) {
+            hints.push(hint);
+        }
+        if let Some(hint) = position_hint(self, &candidates) {
+            hints.push(hint);
+        }
+        if let Some(word) = self.suggest_word(&HashSet::new()) {
+            hints.push(Hint::Word { word });
+        }
+
+        hints
+    }
+
+    /// Whether the guesses so far have shown `letter` is in the word.
+    fn is_known_letter(&self, letter: char) -> bool {
+        self.guesses.iter().any(|(word, statuses)| {
+            word.chars()
+                .zip(statuses)
+                .any(|(c, status)| c == letter && *status != LetterStatus::NotInWord)
+        })
+    }
+
+    fn is_known_position(&self, position: usize) -> bool {
+        self.guesses
+            .iter()
+            .any(|(_, statuses)| statuses.get(position) == Some(&LetterStatus::Correct))
+    }
+}
+
+/// Picks the most common of `counts`, breaking ties alphabetically so hints don't change between calls.
+fn most_common<K: Ord + Copy>(counts: impl Iterator<Item = (K, usize)>) -> Option<(K, usize)> {
+    counts
+        .filter(|(_, count)| *count > 0)
+        .max_by(|(a, a_count), (b, b_count)| a_count.cmp(b_count).then(b.cmp(a)))
+}
+
+/// A letter in every candidate is in the most of them, so `most_common` offers one whenever there is one.
+fn letter_hint(puzzle: &Puzzle, candidates: &[String]) -> Option<Hint> {
+    let counts = ('A'..='Z')
+        .filter(|letter| !puzzle.is_known_letter(*letter))
+        .map(|letter| {
+            let count = candidates.iter().filter(|w| w.contains(letter)).count();
+            (letter, count)
+        });
+
+    most_common(counts).map(|(letter, count)| Hint::Letter {
+        letter,
+        candidates: count,
+        certain: count == candidates.len(),
+    })
+}
+
+fn position_hint(puzzle: &Puzzle, candidates: &[String]) -> Option<Hint> {
+    let length = puzzle.settings().word_length;
+    let counts = (0..length)
+        .filter(|position| !puzzle.is_known_position(*position))
+        .flat_map(|position| ('A'..='Z').map(move |letter| (position, letter)))
+        .map(|(position, letter)| {
+            let count = candidates
+                .iter()
+                .filter(|w| w.as_bytes()[position] == letter as u8)
+                .count();
+            ((position, letter), count)
+        });
+
+    most_common(counts).map(|((position, letter), count)| Hint::Position {
+        letter,
+        position: position + 1,
+        candidates: count,
+        certain: count == candidates.len(),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn hints_reveal_more_each_step() {
+        let mut puzzle = Puzzle::setup();
+        puzzle.assign_guess_from_cli("RUSTY".to_string(), "---.X");
+        puzzle.assign_guess_from_cli("TONEY".to_string(), "X-X-X");
+
+        // TANGY, TANKY and TINNY are left.
+        assert_eq!(
+            vec![
+                Hint::CandidateCount { count: 3 },
+                Hint::Letter {
+                    letter: 'A',
+                    candidates: 2,
+                    certain: false
+                },
+                Hint::Position {
+                    letter: 'A',
+                    position: 2,
+                    candidates: 2,
+                    certain: false
+                },
+                Hint::Word {
+                    word: puzzle.suggest_word(&HashSet::new()).unwrap()
+                },
+            ],
+            puzzle.hints()
+        );
+    }
+
+    #[test]
+    fn offers_a_letter_every_candidate_has() {
+        let words = ["CRANE", "CRATE", "GRACE"].map(|w| w.to_string()).to_vec();
+        let puzzle = Puzzle::with_settings(words, crate::Settings::default());
+
+        assert_eq!(
+            Hint::Letter {
+                letter: 'A',
+                candidates: 3,
+                certain: true
+            },
+            puzzle.hints()[1]
+        );
+    }
+
+    #[test]
+    fn solved_puzzles_have_nothing_left_to_hint() {
+        let mut puzzle = Puzzle::setup();
+        puzzle.assign_guess_from_cli("TANGY".to_string(), "XXXXX");
+
+        let hints = puzzle.hints();
+        assert_eq!(Hint::CandidateCount { count: 1 }, hints[0]);
+        assert!(!hints
+            .iter()
+            .any(|hint| matches!(hint, Hint::Letter { .. } | Hint::Position { .. })));
+    }
+}
@@ -0,0 +1,263 @@
+use crate::cli::Options;
+use helple::{format_feedback, parse_feedback, LetterStatus, Puzzle};
+use serde_json::json;
+use std::collections::HashSet;
+use std::error::Error;
+use std::io;
+
+const HELP: &str = r#"Enter the results for the suggested word, e.g. `---.X`, or the word you played first, e.g. `CRANE -.X--`.
+Other commands:
+  show                    List the guesses so far
+  undo                    Forget the last guess
+  edit N [WORD] RESULTS   Replace guess N
+  reset                   Start over
+  rejected                The game didn't accept the suggestion; suggest another
+  help                    Print this message"#;
+
+/// Something typed at the interactive prompt.
+#[derive(Debug, PartialEq)]
+enum Input {
+    Turn(String, Vec<LetterStatus>),
+    Show,
+    Undo,
+    Edit(usize, String, Vec<LetterStatus>),
+    Reset,
+    /// The game refused this word.
+    Rejected(String),
+    Help,
+}
+
+/// Suggests a word, reads its results and repeats until the puzzle is solved. With `--json`, prints one JSON object per line instead of prose.
+pub fn solve(options: &Options) -> Result<(), Box<dyn Error>> {
+    let mut puzzle = crate::puzzle(options, &options.args)?;
+    if options.json {
+        return solve_json(options, puzzle);
+    }
+
+    let mut buffer = String::new();
+    let stdin = io::stdin();
+    let mut rejected: HashSet<String> = HashSet::new();
+
+    loop {
+        let suggestion = puzzle.suggest_word(&rejected);
+
+        match &suggestion {
+            Some(word) => {
+                println!("Go type <{:?}> into the puzzle. What was the result?", word);
+                println!("(If you played something else, enter the word first, e.g. `CRANE -.X--`. Type `help` for more.)");
+            }
+            None => println!("No suggestion available. 💥 Type `undo`, `edit` or `reset` to fix earlier results."),
+        }
+
+        buffer.clear();
+        if stdin.read_line(&mut buffer)? == 0 {
+            break;
+        }
+
+        match read_input(&puzzle, suggestion, buffer.trim()) {
+            Ok(Input::Turn(word, statuses)) => {
+                puzzle.assign_guess_results(word, statuses);
+                println!("{}", puzzle);
+            }
+            Ok(Input::Show) => show(&puzzle),
+            Ok(Input::Undo) => match puzzle.undo() {
+                Some((word, _)) => {
+                    println!("Forgot {}.", word);
+                    show(&puzzle);
+                }
+                None => println!("Nothing to undo."),
+            },
+            Ok(Input::Edit(index, word, statuses)) => {
+                puzzle.replace_guess(index, word, statuses);
+                show(&puzzle);
+            }
+            Ok(Input::Reset) => {
+                puzzle.reset();
+                println!("Starting over.");
+            }
+            Ok(Input::Rejected(word)) => {
+                println!("Won't suggest {} again.", word);
+                rejected.insert(word);
+            }
+            Ok(Input::Help) => println!("{}", HELP),
+            Err(err) => println!("{}", err),
+        }
+
+        crate::save(options, &puzzle)?;
+
+        if let Some(solution) = puzzle.solution() {
+            crate::record_solution(options, &puzzle)?;
+            println!("Puzzle solved using {}! 🙌 Share your score. 😘", solution);
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+fn solve_json(options: &Options, mut puzzle: Puzzle) -> Result<(), Box<dyn Error>> {
+    let mut buffer = String::new();
+    let stdin = io::stdin();
+    let mut rejected: HashSet<String> = HashSet::new();
+
+    loop {
+        let suggestion = puzzle.suggest_word(&rejected);
+        let guesses: Vec<_> = puzzle
+            .guesses()
+            .iter()
+            .map(|(word, statuses)| json!({ "word": word, "results": format_feedback(statuses) }))
+            .collect();
+        let solution = puzzle.solution();
+
+        println!(
+            "{}",
+            json!({
+                "suggestion": suggestion,
+                "candidates": puzzle.candidates().len(),
+                "guesses": guesses,
+                "solved": solution,
+            })
+        );
+
+        if solution.is_some() {
+            crate::record_solution(options, &puzzle)?;
+            break;
+        }
+
+        buffer.clear();
+        if stdin.read_line(&mut buffer)? == 0 {
+            break;
+        }
+
+        match read_input(&puzzle, suggestion, buffer.trim()) {
+            Ok(Input::Turn(word, statuses)) => puzzle.assign_guess_results(word, statuses),
+            Ok(Input::Show) => {}
+            Ok(Input::Undo) => {
+                puzzle.undo();
+            }
+            Ok(Input::Edit(index, word, statuses)) => {
+                puzzle.replace_guess(index, word, statuses);
+            }
+            Ok(Input::Reset) => puzzle.reset(),
+            Ok(Input::Rejected(word)) => {
+                rejected.insert(word);
+            }
+            Ok(Input::Help) => println!("{}", json!({ "help": HELP })),
+            Err(err) => println!("{}", json!({ "error": err.to_string() })),
+        }
+
+        crate::save(options, &puzzle)?;
+    }
+
+    Ok(())
+}
+
+/// Prints the guesses so far with their results.
+fn show(puzzle: &Puzzle) {
+    if puzzle.guesses().is_empty() {
+        println!("No guesses yet.");
+    }
+
+    for (i, (word, statuses)) in puzzle.guesses().iter().enumerate() {
+        let tiles: String = statuses.iter().map(|s| s.to_string()).collect();
+        println!("{}. {} {}", i + 1, word, tiles);
+    }
+}
+
+/// Reads one line typed at the prompt. Results on their own are for `suggestion`.
+fn read_input(
+    puzzle: &Puzzle,
+    suggestion: Option<String>,
+    input: &str,
+) -> Result<Input, Box<dyn Error>> {
+    let parts: Vec<&str> = input
+        .split(|c: char| c.is_whitespace() || c == ':')
+        .filter(|part| !part.is_empty())
+        .collect();
+
+    match parts.as_slice() {
+        ["show"] => Ok(Input::Show),
+        ["undo"] => Ok(Input::Undo),
+        ["reset"] => Ok(Input::Reset),
+        ["help"] => Ok(Input::Help),
+        ["rejected"] => match suggestion {
+            Some(word) => Ok(Input::Rejected(word)),
+            None => Err("There is no suggestion to reject.".into()),
+        },
+        ["edit", n, rest @ ..] => {
+            let index = match n.parse::<usize>() {
+                Ok(n) if n >= 1 && n <= puzzle.guesses().len() => n - 1,
+                _ => return Err(format!("There is no guess <{}> to edit.", n).into()),
+            };
+            let previous = puzzle.guesses()[index].0.clone();
+            let (word, statuses) = read_turn(puzzle, Some(previous), rest)?;
+
+            Ok(Input::Edit(index, word, statuses))
+        }
+        turn => {
+            let (word, statuses) = read_turn(puzzle, suggestion, turn)?;
+            Ok(Input::Turn(word, statuses))
+        }
+    }
+}
+
+/// Either just the results for `default_word`, or the word that was played followed by its results.
+fn read_turn(
+    puzzle: &Puzzle,
+    default_word: Option<String>,
+    parts: &[&str],
+) -> Result<(String, Vec<LetterStatus>), Box<dyn Error>> {
+    let (word, results) = match (parts, default_word) {
+        ([results], Some(word)) => (word, *results),
+        ([word, results], _) => (word.to_uppercase(), *results),
+        _ => return Err(
+            "Expected the results, or the word you played and its results. Type `help` for more."
+                .into(),
+        ),
+    };
+
+    if !puzzle.dictionary().contains(&word) {
+        return Err(format!("<{}> isn't in the word list.", word).into());
+    }
+
+    let statuses = parse_feedback(results)?;
+    if statuses.len() != word.len() {
+        return Err(format!("Expected {} results for <{}>.", word.len(), word).into());
+    }
+
+    Ok((word, statuses))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn reads_turns_and_commands() {
+        let mut puzzle = Puzzle::setup();
+        let rusty = Some("RUSTY".to_string());
+
+        assert_eq!(
+            Input::Turn("RUSTY".to_string(), parse_feedback("---.X").unwrap()),
+            read_input(&puzzle, rusty.clone(), "---.X").unwrap()
+        );
+        assert_eq!(
+            Input::Turn("CRANE".to_string(), parse_feedback("-.X--").unwrap()),
+            read_input(&puzzle, rusty.clone(), "crane -.X--").unwrap()
+        );
+        assert!(read_input(&puzzle, rusty.clone(), "QWXYZ -.X--").is_err());
+        assert!(read_input(&puzzle, rusty.clone(), "edit 1 -----").is_err());
+        assert_eq!(
+            Input::Rejected("RUSTY".to_string()),
+            read_input(&puzzle, rusty.clone(), "rejected").unwrap()
+        );
+        assert!(read_input(&puzzle, None, "rejected").is_err());
+
+        puzzle.assign_guess_from_cli("RUSTY".to_string(), "-----");
+        assert_eq!(
+            Input::Edit(0, "RUSTY".to_string(), parse_feedback("---.X").unwrap()),
+            read_input(&puzzle, None, "edit 1 ---.X").unwrap()
+        );
+        assert_eq!(Input::Undo, read_input(&puzzle, None, "undo").unwrap());
+    }
+}
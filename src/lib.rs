@@ -1,76 +1,320 @@
+use analysis::GuessRanking;
+use board::Key;
+use config::{ConfigReport, ExtensionConfig};
+use hints::HintReport;
+use serde::{Deserialize, Serialize};
+use session::{Report, Session};
+use std::collections::HashSet;
+use std::fmt;
+use std::str::FromStr;
 use unicode_segmentation::UnicodeSegmentation;
 use wasm_bindgen::prelude::*;
 
+pub mod analysis;
+pub mod board;
+pub mod config;
+pub mod daily;
+pub mod game;
+pub mod hints;
+pub mod session;
+pub mod share;
+pub mod simulation;
+pub mod sites;
+pub mod words;
+
 // When the `wee_alloc` feature is enabled, use `wee_alloc` as the global
 // allocator.
 #[cfg(feature = "wee_alloc")]
 #[global_allocator]
 static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
 
-#[wasm_bindgen(module = "glue-helpers.js")]
-extern "C" {
-    fn console_log(s: &str);
-    fn perform_guess(s: &str);
-    fn get_current_puzzle_state() -> JsValue;
-}
-
-static DICTIONARY: &'static str = include_str!("../support/less-words");
-
-struct Puzzle {
-    #[allow(dead_code)]
+#[derive(Clone)]
+pub struct Puzzle {
     guesses: Vec<(String, Vec<LetterStatus>)>,
     assignments: Vec<Assignment>,
     dictionary: Vec<String>,
+    settings: Settings,
+    /// Words ruled out as answers up front, e.g. earlier days' answers.
+    excluded: HashSet<String>,
 }
 
-use serde::{Deserialize, Serialize};
+/// A possible next guess. The score's meaning depends on the strategy: the heuristic's points or the bits of information the guess is expected to reveal.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Suggestion {
+    pub word: String,
+    pub score: f64,
+}
+
+/// How the next guess is chosen.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Strategy {
+    /// Play the permitted word with the most unique letters and vowels, see `score_for_potential_guess`.
+    Heuristic,
+    /// Play the word expected to reveal the most information about the remaining candidates.
+    Entropy,
+}
+
+impl FromStr for Strategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "heuristic" => Ok(Strategy::Heuristic),
+            "entropy" => Ok(Strategy::Entropy),
+            _ => Err(format!(
+                "Unknown strategy <{}>, expected `heuristic` or `entropy`",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Strategy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Strategy::Heuristic => write!(f, "heuristic"),
+            Strategy::Entropy => write!(f, "entropy"),
+        }
+    }
+}
 
-#[derive(Serialize, Debug, Deserialize)]
-struct JsPuzzleState {
-    guesses: Vec<JsPuzzleGuess>,
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub word_length: usize,
+    /// Only suggest words that use every hint revealed so far, as Wordle's hard mode requires.
+    pub hard_mode: bool,
+    pub strategy: Strategy,
+    /// First word to play. When unset, or the wrong length, the strategy picks one.
+    pub opening: Option<String>,
 }
 
-#[derive(Serialize, Debug, Deserialize)]
-struct JsPuzzleGuess {
-    word: String,
-    results: String,
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            word_length: 5,
+            hard_mode: false,
+            strategy: Strategy::Heuristic,
+            opening: Some("RUSTY".to_string()),
+        }
+    }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
-struct Assignment {
-    letter: char,
-    position: usize,
-    status: LetterStatus,
+pub struct Assignment {
+    pub letter: char,
+    pub position: usize,
+    pub status: LetterStatus,
+}
+
+impl Assignment {
+    /// Whether `word` satisfies this restriction.
+    fn permits(&self, word: &str) -> bool {
+        let at_position = word.as_bytes().get(self.position) == Some(&(self.letter as u8));
+
+        match self.status {
+            LetterStatus::Correct => at_position,
+            LetterStatus::WrongSpot => word.contains(self.letter) && !at_position,
+            LetterStatus::NotInWord => !word.contains(self.letter),
+        }
+    }
+}
+
+impl fmt::Display for Assignment {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.status {
+            LetterStatus::Correct => {
+                write!(
+                    f,
+                    "{} must be at position {}",
+                    self.letter,
+                    self.position + 1
+                )
+            }
+            LetterStatus::WrongSpot => write!(
+                f,
+                "{} must be in the word, but not at position {}",
+                self.letter,
+                self.position + 1
+            ),
+            LetterStatus::NotInWord => write!(f, "{} is not in the word", self.letter),
+        }
+    }
+}
+
+/// How a game stands, see `Puzzle::progress`.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum Progress {
+    Playing,
+    Solved {
+        word: String,
+        guesses: usize,
+    },
+    /// The game is lost, e.g. `Out of guesses`.
+    Failed {
+        reason: String,
+    },
+}
+
+/// Suggestions worked out a batch of guesses at a time, so that slow strategies can report progress or be abandoned, see `Puzzle::suggestion_job`.
+pub struct SuggestionJob {
+    count: usize,
+    exclude: HashSet<String>,
+    suggestions: Vec<Suggestion>,
+    /// Guesses still being scored, for strategies that take a while.
+    ranking: Option<GuessRanking>,
+}
+
+impl SuggestionJob {
+    /// Scores up to `batch` more guesses. Returns whether the suggestions are ready.
+    pub fn step(&mut self, batch: usize) -> bool {
+        if let Some(ranking) = &mut self.ranking {
+            if ranking.step(batch) {
+                let ranked = self.ranking.take().unwrap().finish();
+                self.add(
+                    ranked
+                        .into_iter()
+                        .map(|(word, score)| Suggestion { word, score })
+                        .collect(),
+                );
+            }
+        }
+
+        self.ranking.is_none()
+    }
+
+    /// How much of the work is done, from 0 to 1.
+    pub fn progress(&self) -> f64 {
+        self.ranking
+            .as_ref()
+            .map_or(1.0, |ranking| ranking.progress())
+    }
+
+    /// Does whatever is left and returns up to `count` suggestions, best first.
+    pub fn finish(mut self) -> Vec<Suggestion> {
+        self.step(usize::MAX);
+        self.suggestions
+    }
+
+    /// Takes suggestions from `ranked`, best first, until there are enough.
+    fn add(&mut self, ranked: Vec<Suggestion>) {
+        for suggestion in ranked {
+            if self.suggestions.len() >= self.count {
+                break;
+            }
+            if !self.exclude.contains(&suggestion.word)
+                && self.suggestions.iter().all(|s| s.word != suggestion.word)
+            {
+                self.suggestions.push(suggestion);
+            }
+        }
+    }
+}
+
+/// Why a word is or isn't a possible answer, see `Puzzle::explain`.
+#[derive(Debug, PartialEq)]
+pub enum Explanation {
+    Candidate,
+    /// The word meets every restriction but Wordle wouldn't accept it.
+    NotInWordList,
+    /// Ruled out up front, see `Puzzle::exclude_answers`.
+    Excluded,
+    Eliminated {
+        /// 1-based, or 0 if the restriction wasn't learnt from a guess.
+        guess_number: usize,
+        guess: String,
+        assignment: Assignment,
+    },
+}
+
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Explanation::Candidate => write!(f, "Still a candidate"),
+            Explanation::NotInWordList => {
+                write!(f, "Meets every restriction but isn't in the word list")
+            }
+            Explanation::Excluded => write!(f, "Already used as an answer"),
+            Explanation::Eliminated {
+                guess_number: 0,
+                assignment,
+                ..
+            } => write!(f, "Eliminated because {}", assignment),
+            Explanation::Eliminated {
+                guess_number,
+                guess,
+                assignment,
+            } => write!(
+                f,
+                "Eliminated by guess {} ({}): {}",
+                guess_number, guess, assignment
+            ),
+        }
+    }
 }
 #[derive(Clone, Copy, Debug, PartialEq)]
-enum LetterStatus {
+pub enum LetterStatus {
     Correct,
     WrongSpot,
     NotInWord,
 }
 
 impl Puzzle {
-    fn setup() -> Puzzle {
-        let mut dictionary: Vec<String> = Vec::new();
-        let input = DICTIONARY;
-
-        for word in input.split("\n") {
-            let trimmed = word.trim();
-            if trimmed.len() != 5 {
-                continue;
-            }
+    pub fn setup() -> Puzzle {
+        Puzzle::with_settings(words::dictionary(), Settings::default())
+    }
 
-            dictionary.push(trimmed.to_string().to_uppercase());
-        }
+    /// A puzzle that guesses from `dictionary`. Words of the wrong length are dropped.
+    pub fn with_settings(dictionary: Vec<String>, settings: Settings) -> Puzzle {
+        let dictionary = dictionary
+            .into_iter()
+            .filter(|word| word.len() == settings.word_length)
+            .map(|word| word.to_uppercase())
+            .collect();
 
         Puzzle {
             guesses: vec![],
             assignments: vec![],
             dictionary,
+            settings,
+            excluded: HashSet::new(),
         }
     }
 
-    fn solution(&self) -> Option<String> {
+    pub fn settings(&self) -> &Settings {
+        &self.settings
+    }
+
+    pub fn guesses(&self) -> &[(String, Vec<LetterStatus>)] {
+        &self.guesses
+    }
+
+    pub fn dictionary(&self) -> &[String] {
+        &self.dictionary
+    }
+
+    /// Rules `words` out as answers, whatever the guesses say.
+    pub fn exclude_answers<I: IntoIterator<Item = String>>(&mut self, words: I) {
+        self.excluded
+            .extend(words.into_iter().map(|word| word.to_uppercase()));
+    }
+
+    pub fn excluded(&self) -> &HashSet<String> {
+        &self.excluded
+    }
+
+    /// Words from the dictionary that are still possible answers.
+    pub fn candidates(&self) -> Vec<String> {
+        self.dictionary
+            .iter()
+            .filter(|word| self.is_permitted_word(word))
+            .cloned()
+            .collect()
+    }
+
+    pub fn solution(&self) -> Option<String> {
         let mut correct_letters: Vec<char> = Vec::new();
 
         let mut correct_assignments: Vec<&Assignment> = self
@@ -79,16 +323,16 @@ impl Puzzle {
             .filter(|a| a.status == LetterStatus::Correct)
             .clone()
             .collect();
-        correct_assignments.sort_by(|a, b| a.position.cmp(&b.position));
+        correct_assignments.sort_by_key(|a| a.position);
 
         assert!(
-            correct_assignments.len() <= 5, // TODO: use real length depending on game
+            correct_assignments.len() <= self.settings.word_length,
             "Found {} 'correct' letters assigned. self.assignments={:?}",
             correct_letters.len(),
             self.assignments
         );
 
-        if correct_assignments.len() != 5 {
+        if correct_assignments.len() != self.settings.word_length {
             return None;
         }
 
@@ -99,88 +343,168 @@ impl Puzzle {
         Some(correct_letters.iter().collect())
     }
 
-    fn is_permitted_word(&self, word: &String) -> bool {
-        let debug = false; // *word == "KNOLL".to_string();
-        for a in &self.assignments {
-            if debug {
-                println!("Looking at rule {:?}", a);
+    /// Whether the last guess solved the puzzle, or the game was lost with `max_guesses` used or no words left.
+    pub fn progress(&self, max_guesses: usize) -> Progress {
+        if let Some(word) = self.solution() {
+            if self.guesses.last().is_some_and(|(guess, _)| *guess == word) {
+                return Progress::Solved {
+                    word,
+                    guesses: self.guesses.len(),
+                };
             }
-            let pass = match a.status {
-                LetterStatus::Correct => word.as_bytes()[a.position] == a.letter as u8,
-                LetterStatus::WrongSpot => {
-                    let result =
-                        word.contains(a.letter) && word.as_bytes()[a.position] != a.letter as u8;
-
-                    if debug {
-                        println!("WrongSpot returning {} because:", result);
-                        println!(
-                            " - word.contains({})? {}",
-                            a.letter,
-                            word.contains(a.letter)
-                        );
-                        println!(" - word[{}] = {}", a.position, word.as_bytes()[a.position]);
-                        println!(" - a.letter={}", a.letter as u8);
-                    }
-
-                    result
-                }
-                LetterStatus::NotInWord => {
-                    if debug {
-                        println!(
-                            "NotInWord: word contains {}? {}, so returning {}",
-                            a.letter,
-                            word.contains(a.letter),
-                            !word.contains(a.letter)
-                        );
-                    }
-                    !word.contains(a.letter)
-                }
-            };
+        }
 
-            if !pass {
-                if debug {
-                    println!(
-                        "Word <{}> was rejected because it doesn't meet the requirements for {:?}",
-                        word, a
-                    );
-                }
-                return false;
+        if self.guesses.len() >= max_guesses {
+            Progress::Failed {
+                reason: "Out of guesses".to_string(),
+            }
+        } else if self.candidates().is_empty() {
+            Progress::Failed {
+                reason: "No words fit the results".to_string(),
             }
+        } else {
+            Progress::Playing
         }
+    }
 
-        true
+    fn is_permitted_word(&self, word: &str) -> bool {
+        self.first_violation(word).is_none() && !self.excluded.contains(word)
     }
 
-    fn suggest_word(&self) -> Option<String> {
-        #[derive(Debug)]
-        struct Suggestion {
-            score: usize,
-            word: String,
+    /// The first known restriction that `word` breaks, if any.
+    fn first_violation(&self, word: &str) -> Option<&Assignment> {
+        self.assignments.iter().find(|a| !a.permits(word))
+    }
+
+    /// Explains why `word` is, or is no longer, a possible answer given the guesses so far.
+    pub fn explain(&self, word: &str) -> Explanation {
+        let word = word.to_uppercase();
+
+        if let Some(a) = self.first_violation(&word) {
+            // Find the guess that taught us this restriction.
+            let guess = self.guesses.iter().position(|(guessed, statuses)| {
+                guessed.as_bytes().get(a.position) == Some(&(a.letter as u8))
+                    && statuses.get(a.position) == Some(&a.status)
+            });
+
+            return match guess {
+                Some(i) => Explanation::Eliminated {
+                    guess_number: i + 1,
+                    guess: self.guesses[i].0.clone(),
+                    assignment: *a,
+                },
+                None => Explanation::Eliminated {
+                    guess_number: 0,
+                    guess: String::new(),
+                    assignment: *a,
+                },
+            };
         }
 
-        if self.assignments.len() == 0 {
-            return Some("RUSTY".to_string());
+        if !self.dictionary.contains(&word) {
+            return Explanation::NotInWordList;
         }
 
-        let mut permitted: Vec<Suggestion> = Vec::new();
-        for word in &self.dictionary {
-            if self.is_permitted_word(&word) {
-                let score = score_for_potential_guess(word);
-                permitted.push(Suggestion {
-                    score,
-                    word: word.clone(),
-                });
+        if self.excluded.contains(&word) {
+            return Explanation::Excluded;
+        }
+
+        Explanation::Candidate
+    }
+
+    /// The best guess that isn't in `exclude`, e.g. words the game has refused.
+    pub fn suggest_word(&self, exclude: &HashSet<String>) -> Option<String> {
+        self.suggestions_excluding(1, exclude)
+            .pop()
+            .map(|suggestion| suggestion.word)
+    }
+
+    /// Up to `count` guesses, best first, scored by the puzzle's strategy.
+    pub fn suggestions(&self, count: usize) -> Vec<Suggestion> {
+        self.suggestions_excluding(count, &HashSet::new())
+    }
+
+    /// Like `suggestions`, but never suggesting a word in `exclude`.
+    pub fn suggestions_excluding(
+        &self,
+        count: usize,
+        exclude: &HashSet<String>,
+    ) -> Vec<Suggestion> {
+        self.suggestion_job(count, exclude.clone()).finish()
+    }
+
+    /// The work of `suggestions_excluding`, to be done a batch at a time, see `SuggestionJob`.
+    pub fn suggestion_job(&self, count: usize, mut exclude: HashSet<String>) -> SuggestionJob {
+        // Words already guessed without winning would only teach us what we know. Repeated letters can leave them permitted, so rule them out here.
+        exclude.extend(
+            self.guesses
+                .iter()
+                .filter(|(_, statuses)| statuses.iter().any(|s| *s != LetterStatus::Correct))
+                .map(|(word, _)| word.clone()),
+        );
+
+        let mut job = SuggestionJob {
+            count,
+            exclude,
+            suggestions: Vec::new(),
+            ranking: None,
+        };
+
+        if self.assignments.is_empty() {
+            if let Some(opening) = &self.settings.opening {
+                let opening = opening.to_uppercase();
+                if opening.len() == self.settings.word_length && !job.exclude.contains(&opening) {
+                    // The opening word is played as is, so it isn't scored.
+                    job.suggestions.push(Suggestion {
+                        word: opening,
+                        score: 0.0,
+                    });
+                }
             }
         }
 
-        permitted.sort_by(|a, b| a.score.cmp(&b.score));
+        if job.suggestions.len() < count {
+            match self.settings.strategy {
+                Strategy::Heuristic => job.add(self.rank_by_heuristic()),
+                Strategy::Entropy => job.ranking = Some(self.information_ranking()),
+            }
+        }
 
-        // println!("Suggestions sorted by score:\n{:?}", permitted);
+        job
+    }
 
-        match permitted.pop() {
-            Some(suggestion) => Some(suggestion.word.to_uppercase()),
-            None => None,
+    fn rank_by_heuristic(&self) -> Vec<Suggestion> {
+        let mut permitted: Vec<(usize, String)> = Vec::new();
+        for word in &self.dictionary {
+            if self.is_permitted_word(word) {
+                permitted.push((score_for_potential_guess(word), word.clone()));
+            }
         }
+
+        // Among equal scores the word furthest down the dictionary has always won.
+        permitted.sort_by_key(|(score, _)| *score);
+        permitted.reverse();
+
+        permitted
+            .into_iter()
+            .map(|(score, word)| Suggestion {
+                word,
+                score: score as f64,
+            })
+            .collect()
+    }
+
+    fn information_ranking(&self) -> GuessRanking {
+        let candidates = self.candidates();
+
+        // With two or fewer left, guessing one of them is never worse than narrowing them down.
+        let guesses = if candidates.len() <= 2 || self.settings.hard_mode {
+            candidates.clone()
+        } else {
+            self.dictionary.clone()
+        };
+
+        GuessRanking::new(candidates, guesses)
     }
 
     #[allow(dead_code)]
@@ -197,7 +521,7 @@ impl Puzzle {
         }
     }
 
-    fn assign_guess_results(&mut self, word: String, letter_statuses: Vec<LetterStatus>) {
+    pub fn assign_guess_results(&mut self, word: String, letter_statuses: Vec<LetterStatus>) {
         assert!(
             word.len() == letter_statuses.len(),
             "Guessed word <{}> length must match letter statuses exactly: <{:?}>",
@@ -232,40 +556,115 @@ impl Puzzle {
         self.guesses.push((word.to_string(), letter_statuses));
     }
 
-    fn assign_guess_from_cli(&mut self, word: String, input: &str) {
-        let mut letter_statuses: Vec<LetterStatus> = Vec::new();
+    /// Forgets the most recent guess, returning it.
+    pub fn undo(&mut self) -> Option<(String, Vec<LetterStatus>)> {
+        let last = self.guesses.pop();
+        self.rebuild();
+        last
+    }
 
-        for (i, grapheme) in input.graphemes(true).enumerate() {
-            let letter_status = match grapheme {
-                "X" => Some(LetterStatus::Correct),
-                "." => {
-                    // Partial hit
-                    Some(LetterStatus::WrongSpot)
-                }
-                "-" => Some(LetterStatus::NotInWord),
-                _ => None,
-            };
+    /// Replaces guess `index` (0-based) and everything learnt from it. Returns `false` if there is no such guess.
+    pub fn replace_guess(
+        &mut self,
+        index: usize,
+        word: String,
+        letter_statuses: Vec<LetterStatus>,
+    ) -> bool {
+        if index >= self.guesses.len() {
+            return false;
+        }
+
+        self.guesses[index] = (word, letter_statuses);
+        self.rebuild();
+        true
+    }
+
+    /// Forgets every guess, back to a fresh puzzle with the same settings.
+    pub fn reset(&mut self) {
+        self.guesses.clear();
+        self.assignments.clear();
+    }
+
+    /// Recomputes the assignments from the stored guesses.
+    fn rebuild(&mut self) {
+        let guesses = std::mem::take(&mut self.guesses);
+        self.assignments.clear();
+
+        for (word, letter_statuses) in guesses {
+            self.assign_guess_results(word, letter_statuses);
+        }
+    }
+
+    pub fn assign_guess_from_cli(&mut self, word: String, input: &str) {
+        match parse_feedback(input) {
+            Ok(letter_statuses) => self.assign_guess_results(word, letter_statuses),
+            Err(err) => println!("{}", err),
+        }
+    }
+}
 
-            if let Some(status) = letter_status {
-                letter_statuses.push(status);
-            } else {
-                println!("Unexpected <{}> in input at character {}.", grapheme, i);
-                println!(
-                    r#"Expected format for puzzle results:
+#[derive(Debug, PartialEq)]
+pub struct FeedbackError {
+    pub grapheme: String,
+    pub position: usize,
+}
+
+impl fmt::Display for FeedbackError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            r#"Unexpected <{}> in input at character {}.
+Expected format for puzzle results:
 `X` = direct hit (right letter in right position
 `.` = partial hit (right letter in wrong position)
-`-` = complete miss (letter not in word)"#
-                );
-                return;
+`-` = complete miss (letter not in word)"#,
+            self.grapheme, self.position
+        )
+    }
+}
+
+impl std::error::Error for FeedbackError {}
+
+/// Parses puzzle results as typed on the command line, e.g. `---.X`.
+pub fn parse_feedback(input: &str) -> Result<Vec<LetterStatus>, FeedbackError> {
+    let mut letter_statuses: Vec<LetterStatus> = Vec::new();
+
+    for (i, grapheme) in input.graphemes(true).enumerate() {
+        let letter_status = match grapheme {
+            "X" => LetterStatus::Correct,
+            "." => {
+                // Partial hit
+                LetterStatus::WrongSpot
             }
-        }
+            "-" => LetterStatus::NotInWord,
+            _ => {
+                return Err(FeedbackError {
+                    grapheme: grapheme.to_string(),
+                    position: i,
+                })
+            }
+        };
 
-        self.assign_guess_results(word, letter_statuses);
+        letter_statuses.push(letter_status);
     }
+
+    Ok(letter_statuses)
+}
+
+/// Types results the way `parse_feedback` reads them.
+pub fn format_feedback(statuses: &[LetterStatus]) -> String {
+    statuses
+        .iter()
+        .map(|s| match s {
+            LetterStatus::Correct => 'X',
+            LetterStatus::WrongSpot => '.',
+            LetterStatus::NotInWord => '-',
+        })
+        .collect()
 }
 
 /// When guessing a word, we want to "pin" and elimiate letters as fast as possible. Priorty is given to words that use the most unique letters. Further priority is given to words with the most vowels.
-fn score_for_potential_guess(word: &String) -> usize {
+fn score_for_potential_guess(word: &str) -> usize {
     let mut score = 100;
 
     for grapheme in word.graphemes(true) {
@@ -285,89 +684,262 @@ fn score_for_potential_guess(word: &String) -> usize {
     score
 }
 
-#[wasm_bindgen]
-pub fn perform_next_guess() {
-    let state = get_current_puzzle_state().into_serde::<JsPuzzleState>();
-    match state {
-        Ok(state) => {
-            console_log(format!("@perform_next_guess with current state: {:?}", state).as_str());
+/// Computes the feedback Wordle would give for `guess` when the answer is `answer`. Letters are matched greedily: exact hits first, then each remaining copy of a letter in the answer can mark at most one misplaced copy in the guess, left to right.
+pub fn evaluate_guess(guess: &str, answer: &str) -> Vec<LetterStatus> {
+    let mut statuses = Vec::with_capacity(guess.len());
+    evaluate_guess_into(guess.as_bytes(), answer.as_bytes(), &mut statuses);
+    statuses
+}
 
-            let mut puzzle = Puzzle::setup();
+/// Allocation-free variant of `evaluate_guess` for hot loops over whole word lists.
+pub(crate) fn evaluate_guess_into(guess: &[u8], answer: &[u8], statuses: &mut Vec<LetterStatus>) {
+    let mut unmatched = [0u8; 256];
+
+    statuses.clear();
+    for (g, a) in guess.iter().zip(answer) {
+        if g == a {
+            statuses.push(LetterStatus::Correct);
+        } else {
+            statuses.push(LetterStatus::NotInWord);
+            unmatched[*a as usize] += 1;
+        }
+    }
 
-            for guess in state.guesses {
-                // console_log(format!("assigning guess {:?}", guess).as_str());
-                puzzle.assign_guess_from_cli(guess.word.to_uppercase(), guess.results.as_str());
-            }
+    for (i, g) in guess.iter().enumerate() {
+        if statuses[i] == LetterStatus::Correct {
+            continue;
+        }
 
-            let suggestion = puzzle.suggest_word();
-            match suggestion {
-                Some(word) => perform_guess(word.to_lowercase().as_str()),
-                None => console_log("No guess available"),
-            };
+        if unmatched[*g as usize] > 0 {
+            unmatched[*g as usize] -= 1;
+            statuses[i] = LetterStatus::WrongSpot;
         }
-        Err(err) => {
-            console_log(
-                format!(
-                    "@perform_next_guess failed to deserialize incoming state: {:?}",
-                    err
-                )
-                .as_str(),
-            );
+    }
+}
+
+impl fmt::Display for Puzzle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut display = "".to_string();
+
+        for (_word, statuses) in &self.guesses {
+            for s in statuses {
+                display.push_str(format!("{}", s).as_str());
+            }
+            display.push('\n');
         }
+
+        write!(f, "{}", display)
+    }
+}
+
+impl fmt::Display for LetterStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match self {
+            LetterStatus::Correct => "🟩",
+            LetterStatus::WrongSpot => "🟨",
+            LetterStatus::NotInWord => "⬜",
+        };
+
+        write!(f, "{}", s)
     }
 }
 
+/// Suggestions for a puzzle state shaped like a `Session`, e.g. `{guesses: [{word: "rusty", results: "---.X"}]}`. Returns a `session::Report` with the top `count` suggestions, the words still left, or an error.
 #[wasm_bindgen]
-pub fn run() -> String {
-    //    console_log("Hello from Rust run!");
+pub fn suggest(state: JsValue, count: usize) -> JsValue {
+    #[allow(deprecated)]
+    let report = match state.into_serde::<Session>() {
+        Ok(session) => session.report(words::dictionary(), count.max(1)),
+        Err(err) => Report::error(format!("Couldn't read puzzle state: {}", err)),
+    };
 
-    let puzzle = Puzzle::setup();
+    #[allow(deprecated)]
+    JsValue::from_serde(&report).unwrap_or(JsValue::NULL)
+}
 
-    let suggestion = puzzle.suggest_word();
+/// Progressively more revealing hints for a puzzle state shaped like a `Session`, see `Puzzle::hints`. Returns a `hints::HintReport`.
+#[wasm_bindgen]
+pub fn hints(state: JsValue) -> JsValue {
+    #[allow(deprecated)]
+    let report = match state.into_serde::<Session>() {
+        Ok(session) => match session.to_puzzle(words::dictionary()) {
+            Ok(puzzle) => HintReport {
+                hints: puzzle.hints(),
+                error: None,
+            },
+            Err(err) => HintReport {
+                hints: vec![],
+                error: Some(err.to_string()),
+            },
+        },
+        Err(err) => HintReport {
+            hints: vec![],
+            error: Some(format!("Couldn't read puzzle state: {}", err)),
+        },
+    };
 
-    match suggestion {
-        Some(word) => perform_guess(word.as_str()),
-        None => console_log("No guess available"),
+    #[allow(deprecated)]
+    JsValue::from_serde(&report).unwrap_or(JsValue::NULL)
+}
+
+/// The puzzle state on the page's game board, ready for `suggest` or `hints`. Throws if the board can't be read.
+#[wasm_bindgen]
+pub fn read_board() -> Result<JsValue, JsValue> {
+    let session = sites::find_board()
+        .and_then(|board| board.read_state())
+        .map_err(|err| JsValue::from_str(&err.to_string()))?;
+
+    #[allow(deprecated)]
+    JsValue::from_serde(&session).map_err(|err| JsValue::from_str(&err.to_string()))
+}
+
+/// The word typed into the page's game board but not submitted yet, or `undefined` if the row is empty. Throws if the board can't be read.
+#[wasm_bindgen]
+pub fn typed_word() -> Result<Option<String>, JsValue> {
+    sites::find_board()
+        .and_then(|board| board.typed_word())
+        .map_err(|err| JsValue::from_str(&err.to_string()))
+}
+
+/// Reads extension options saved in storage, see `config::ExtensionConfig`. Returns a `config::ConfigReport` with the validated options, or why they were rejected.
+#[wasm_bindgen]
+pub fn validate_config(config: JsValue) -> JsValue {
+    let report = match read_config(config) {
+        Ok(config) => ConfigReport {
+            config: Some(config),
+            error: None,
+        },
+        Err(error) => ConfigReport {
+            config: None,
+            error: Some(error),
+        },
     };
 
-    "Wish I could see this returned string in JS land".to_string()
+    #[allow(deprecated)]
+    JsValue::from_serde(&report).unwrap_or(JsValue::NULL)
+}
 
-    // loop {
-    //     let suggestion = puzzle.suggest_word();
+fn read_config(config: JsValue) -> Result<ExtensionConfig, String> {
+    #[allow(deprecated)]
+    let config = config
+        .into_serde::<ExtensionConfig>()
+        .map_err(|err| format!("Couldn't read options: {}", err))?;
 
-    //     match suggestion {
-    //         Some(word) => {
-    //             println!("Go type <{:?}> into the puzzle. What was the result?", word);
+    config
+        .validate(&words::dictionary())
+        .map_err(|err| err.to_string())
+}
 
-    //             stdin.read_line(&mut buffer)?;
-    //             let input = buffer.trim().clone();
+/// The page's board as the extension hands it to the solver, as returned to JavaScript: how the game stands, and the puzzle state with the solver settings from the options, ready for a `Suggester`.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct BoardState {
+    pub progress: Progress,
+    pub state: Session,
+}
 
-    //             puzzle.assign_guess_from_cli(word, input);
-    //             println!("{}", puzzle);
-    //             buffer.clear();
-    //         }
-    //         None => {
-    //             println!("No suggestion available. 💥");
-    //             break;
-    //         }
-    //     }
+/// Reads the board with the solver settings from `config`, an `ExtensionConfig`. Returns a `BoardState` without ranking any guesses, so the ranking can run in a Web Worker; the caller types the word it picks with `press_key`, at the pace `config` asks for. Throws if the board or `config` can't be read.
+#[wasm_bindgen]
+pub fn board_state(config: JsValue) -> Result<JsValue, JsValue> {
+    let error = |err: &dyn std::error::Error| JsValue::from_str(&err.to_string());
+
+    let config = read_config(config).map_err(|err| JsValue::from_str(&err))?;
+    let board = sites::find_board().map_err(|err| error(&err))?;
+    let mut state = board.read_state().map_err(|err| error(&err))?;
+    state.settings = config.settings();
+    let puzzle = state
+        .to_puzzle(words::dictionary())
+        .map_err(|err| error(&err))?;
+
+    let board_state = BoardState {
+        progress: puzzle.progress(board.max_guesses()),
+        state,
+    };
+
+    #[allow(deprecated)]
+    JsValue::from_serde(&board_state).map_err(|err| JsValue::from_str(&err.to_string()))
+}
 
-    //     if let Some(solution) = puzzle.solution() {
-    //         println!("Puzzle solved using {}! 🙌 Share your score. 😘", solution);
-    //         break;
-    //     }
-    // }
+/// Uppercased words from a JavaScript array of strings. Anything else counts as no words.
+fn read_words(words: JsValue) -> HashSet<String> {
+    #[allow(deprecated)]
+    let words = words.into_serde::<Vec<String>>().unwrap_or_default();
+
+    words.into_iter().map(|word| word.to_uppercase()).collect()
+}
+
+/// Suggestions for a puzzle state shaped like a `Session`, worked out a batch of guesses at a time so a Web Worker can report progress and take a cancel message in between. Call `step` until it returns true, then `report`. Freeing it part way abandons the work.
+#[wasm_bindgen]
+pub struct Suggester {
+    job: SuggestionJob,
+    candidates: Vec<String>,
 }
 
+#[wasm_bindgen]
+impl Suggester {
+    /// Starts on the top `count` suggestions, never suggesting or counting a word in `rejected`. Throws if the state can't be read.
+    #[wasm_bindgen(constructor)]
+    pub fn new(state: JsValue, rejected: JsValue, count: usize) -> Result<Suggester, JsValue> {
+        #[allow(deprecated)]
+        let session = state
+            .into_serde::<Session>()
+            .map_err(|err| JsValue::from_str(&format!("Couldn't read puzzle state: {}", err)))?;
+        let mut puzzle = session
+            .to_puzzle(words::dictionary())
+            .map_err(|err| JsValue::from_str(&err.to_string()))?;
+
+        let rejected = read_words(rejected);
+        puzzle.exclude_answers(rejected.iter().cloned());
+
+        Ok(Suggester {
+            job: puzzle.suggestion_job(count.max(1), rejected),
+            candidates: puzzle.candidates(),
+        })
+    }
+
+    /// Scores up to `batch` more guesses. Returns whether the report is ready.
+    pub fn step(&mut self, batch: usize) -> bool {
+        self.job.step(batch)
+    }
+
+    /// How much of the work is done, from 0 to 1.
+    pub fn progress(&self) -> f64 {
+        self.job.progress()
+    }
+
+    /// The `session::Report`, finishing any work that's left.
+    pub fn report(self) -> JsValue {
+        let report = Report {
+            suggestions: self.job.finish(),
+            candidate_count: self.candidates.len(),
+            remaining: self.candidates,
+            error: None,
+        };
+
+        #[allow(deprecated)]
+        JsValue::from_serde(&report).unwrap_or(JsValue::NULL)
+    }
+}
+
+/// Presses a key on the page's keyboard: a letter, `Enter` or `Backspace`. Throws if there's no such key.
+#[wasm_bindgen]
+pub fn press_key(key: &str) -> Result<(), JsValue> {
+    let error = |err: &dyn std::error::Error| JsValue::from_str(&err.to_string());
+
+    let key = key.parse::<Key>().map_err(|err| error(&err))?;
+    sites::find_board()
+        .and_then(|board| board.press_key(key))
+        .map_err(|err| error(&err))
+}
+
+#[cfg(test)]
 mod test {
-    #[allow(unused_imports)]
     use super::*;
 
-    //#[test]
+    #[test]
     fn scores() {
         fn assert_score_better_than(better: &str, lesser: &str) {
-            let better_score = score_for_potential_guess(&better.to_string());
-            let lesser_score = score_for_potential_guess(&lesser.to_string());
+            let better_score = score_for_potential_guess(better);
+            let lesser_score = score_for_potential_guess(lesser);
 
             assert!(
                 better_score > lesser_score,
@@ -379,12 +951,12 @@ mod test {
             );
         }
 
-        assert_eq!(102, score_for_potential_guess(&"RUSTY".to_string()));
+        assert_eq!(102, score_for_potential_guess("RUSTY"));
         assert_score_better_than("RUSTY", "GREEN");
         assert_score_better_than("AEIOU", "RUSTY");
     }
 
-    //#[test]
+    #[test]
     fn solution() {
         let mut puzzle = super::Puzzle::setup();
 
@@ -431,6 +1003,53 @@ mod test {
         assert_eq!(Some("ABBEY".to_string()), puzzle.solution());
     }
 
+    #[test]
+    fn progress() {
+        let mut puzzle = Puzzle::setup();
+        puzzle.assign_guess_from_cli("RUSTY".to_string(), "---.X");
+        assert_eq!(Progress::Playing, puzzle.progress(6));
+        assert_eq!(
+            Progress::Failed {
+                reason: "Out of guesses".to_string()
+            },
+            puzzle.progress(1)
+        );
+
+        puzzle.assign_guess_from_cli("TANGY".to_string(), "XXXXX");
+        assert_eq!(
+            Progress::Solved {
+                word: "TANGY".to_string(),
+                guesses: 2
+            },
+            puzzle.progress(2)
+        );
+
+        let mut puzzle = Puzzle::setup();
+        puzzle.assign_guess_from_cli("RUSTY".to_string(), "XXXX-");
+        puzzle.assign_guess_from_cli("BLOCK".to_string(), ".----");
+        assert_eq!(
+            Progress::Failed {
+                reason: "No words fit the results".to_string()
+            },
+            puzzle.progress(6)
+        );
+    }
+
+    #[test]
+    fn evaluate_guess_repeated_letters() {
+        use LetterStatus::*;
+
+        assert_eq!(
+            vec![NotInWord, NotInWord, Correct, Correct, NotInWord],
+            evaluate_guess("OVOLI", "KNOLL")
+        );
+        assert_eq!(
+            vec![WrongSpot, NotInWord, NotInWord, NotInWord, NotInWord],
+            evaluate_guess("LLAMA", "KNOLS")
+        );
+        assert_eq!(vec![Correct; 5], evaluate_guess("TANGY", "TANGY"));
+    }
+
     #[test]
     fn jan_14() {
         let mut puzzle = super::Puzzle::setup();
@@ -446,8 +1065,6 @@ mod test {
             ],
         );
 
-        let mut suggestion = puzzle.suggest_word();
-
         puzzle.assign_guess_results(
             "TONEY".to_string(),
             vec![
@@ -459,8 +1076,6 @@ mod test {
             ],
         );
 
-        suggestion = puzzle.suggest_word();
-
         puzzle.assign_guess_results(
             "TANKY".to_string(),
             vec![
@@ -472,8 +1087,6 @@ mod test {
             ],
         );
 
-        suggestion = puzzle.suggest_word();
-
         puzzle.assign_guess_results(
             "TANGY".to_string(),
             vec![
@@ -485,7 +1098,7 @@ mod test {
             ],
         );
 
-        suggestion = puzzle.suggest_word();
+        let suggestion = puzzle.suggest_word(&HashSet::new());
 
         assert_eq!(Some("TANGY".to_string()), suggestion);
         assert_eq!(puzzle.solution(), Some("TANGY".to_string()));
@@ -538,7 +1151,140 @@ mod test {
             ],
         );
 
-        let suggestion = puzzle.suggest_word();
+        let suggestion = puzzle.suggest_word(&HashSet::new());
         assert_eq!(Some("KNOLL".to_string()), suggestion);
     }
+
+    #[test]
+    fn explain() {
+        let mut puzzle = super::Puzzle::setup();
+
+        puzzle.assign_guess_from_cli("SOARE".to_string(), "-.---");
+        puzzle.assign_guess_from_cli("CLOUD".to_string(), "-.X--");
+
+        assert_eq!(Explanation::Candidate, puzzle.explain("knoll"));
+        assert_eq!(Explanation::NotInWordList, puzzle.explain("KNOLZ"));
+        assert_eq!(
+            Explanation::Eliminated {
+                guess_number: 2,
+                guess: "CLOUD".to_string(),
+                assignment: Assignment {
+                    letter: 'C',
+                    position: 0,
+                    status: LetterStatus::NotInWord,
+                },
+            },
+            puzzle.explain("CLOUD")
+        );
+        assert_eq!(
+            "Eliminated by guess 1 (SOARE): O must be in the word, but not at position 2",
+            puzzle.explain("MOLDY").to_string()
+        );
+    }
+
+    #[test]
+    fn ranked_suggestions() {
+        let mut puzzle = super::Puzzle::setup();
+
+        assert_eq!(
+            vec![Suggestion {
+                word: "RUSTY".to_string(),
+                score: 0.0
+            }],
+            puzzle.suggestions(1)
+        );
+
+        puzzle.assign_guess_from_cli("RUSTY".to_string(), "---.X");
+
+        let suggestions = puzzle.suggestions(3);
+        assert_eq!(3, suggestions.len());
+        assert_eq!(
+            puzzle.suggest_word(&HashSet::new()),
+            Some(suggestions[0].word.clone())
+        );
+        assert!(suggestions[0].score >= suggestions[1].score);
+        assert!(suggestions[1].score >= suggestions[2].score);
+    }
+
+    #[test]
+    fn undo_and_edit_rebuild_assignments() {
+        let mut fresh = super::Puzzle::setup();
+        fresh.assign_guess_from_cli("RUSTY".to_string(), "---.X");
+        let expected = fresh.suggest_word(&HashSet::new());
+
+        let mut puzzle = super::Puzzle::setup();
+        puzzle.assign_guess_from_cli("RUSTY".to_string(), "-----");
+        assert_ne!(expected, puzzle.suggest_word(&HashSet::new()));
+
+        // Fix the mistyped results
+        assert!(puzzle.replace_guess(0, "RUSTY".to_string(), parse_feedback("---.X").unwrap()));
+        assert_eq!(expected, puzzle.suggest_word(&HashSet::new()));
+        assert!(!puzzle.replace_guess(1, "RUSTY".to_string(), vec![]));
+
+        puzzle.assign_guess_from_cli("TONEY".to_string(), "X-X-X");
+        assert_eq!(
+            Some(("TONEY".to_string(), parse_feedback("X-X-X").unwrap())),
+            puzzle.undo()
+        );
+        assert_eq!(expected, puzzle.suggest_word(&HashSet::new()));
+        assert_eq!(1, puzzle.guesses().len());
+
+        puzzle.reset();
+        assert!(puzzle.guesses().is_empty());
+        assert_eq!(
+            Some("RUSTY".to_string()),
+            puzzle.suggest_word(&HashSet::new())
+        );
+    }
+
+    #[test]
+    fn suggestion_jobs_work_in_batches() {
+        let mut puzzle = super::Puzzle::with_settings(
+            words::dictionary(),
+            Settings {
+                strategy: Strategy::Entropy,
+                ..Settings::default()
+            },
+        );
+        puzzle.assign_guess_from_cli("RUSTY".to_string(), "---.X");
+
+        let mut job = puzzle.suggestion_job(3, HashSet::new());
+        assert_eq!(0.0, job.progress());
+        assert!(!job.step(100));
+        assert!(job.progress() > 0.0 && job.progress() < 1.0);
+        while !job.step(1000) {}
+        assert_eq!(1.0, job.progress());
+
+        assert_eq!(puzzle.suggestions(3), job.finish());
+    }
+
+    #[test]
+    fn suggestions_skip_excluded_words() {
+        let mut puzzle = super::Puzzle::setup();
+        let rejected: HashSet<String> = ["RUSTY".to_string()].into_iter().collect();
+        let next_best = puzzle.suggestions(2)[1].word.clone();
+        assert_eq!(Some(next_best), puzzle.suggest_word(&rejected));
+
+        puzzle.assign_guess_from_cli("RUSTY".to_string(), "---.X");
+        let best = puzzle.suggest_word(&HashSet::new()).unwrap();
+        let rejected: HashSet<String> = [best.clone()].into_iter().collect();
+        let suggestion = puzzle.suggest_word(&rejected).unwrap();
+        assert_ne!(best, suggestion);
+        assert_eq!(puzzle.suggestions(2)[1].word, suggestion);
+    }
+
+    #[test]
+    fn excluded_answers_are_not_candidates() {
+        let mut puzzle = super::Puzzle::setup();
+        puzzle.assign_guess_from_cli("RUSTY".to_string(), "---.X");
+        puzzle.assign_guess_from_cli("TONEY".to_string(), "X-X-X");
+        puzzle.exclude_answers(vec!["tanky".to_string()]);
+
+        assert_eq!(vec!["TANGY", "TINNY"], puzzle.candidates());
+        assert_eq!(Explanation::Excluded, puzzle.explain("TANKY"));
+
+        // Exclusions outlive the guesses they were made alongside.
+        puzzle.reset();
+        assert!(!puzzle.candidates().contains(&"TANKY".to_string()));
+    }
 }
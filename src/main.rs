@@ -1,303 +1,616 @@
-use std::io::BufRead;
-use std::{fmt, fs::File, io};
-use unicode_segmentation::UnicodeSegmentation;
-
-struct Puzzle {
-    #[allow(dead_code)]
-    guesses: Vec<(String, Vec<LetterStatus>)>,
-    assignments: Vec<Assignment>,
-    dictionary: Vec<String>,
+use helple::session::Session;
+use helple::{
+    analysis, daily, format_feedback, parse_feedback, share, simulation, words, Explanation,
+    LetterStatus, Puzzle,
+};
+use serde_json::{json, Value};
+use std::collections::HashSet;
+use std::error::Error;
+use std::io::{self, Read, Write};
+use std::{fs, process};
+
+mod cli;
+mod interactive;
+mod play;
+mod tui;
+
+use cli::{Command, Options};
+
+fn main() {
+    let mut options = match cli::parse(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(err) => {
+            eprintln!("{}\n\n{}", err, cli::USAGE);
+            process::exit(2);
+        }
+    };
+
+    if let Some(path) = options.load.clone() {
+        match fs::read_to_string(&path)
+            .map_err(|err| err.to_string())
+            .and_then(|json| Session::from_json(&json).map_err(|err| err.to_string()))
+            .and_then(|session| options.resume(session))
+        {
+            Ok(()) => {}
+            Err(err) if options.json => {
+                println!("{}", json!({ "error": format!("{}: {}", path, err) }));
+                process::exit(1);
+            }
+            Err(err) => {
+                eprintln!("{}: {}", path, err);
+                process::exit(1);
+            }
+        }
+    }
+
+    let result = match options.command {
+        Command::Solve => interactive::solve(&options),
+        Command::Tui => tui::run(&options),
+        Command::Suggest => suggest(&options),
+        Command::Filter => filter(&options),
+        Command::Simulate => simulate(&options),
+        Command::Explain => explain(&options),
+        Command::Analyze => analyze(&options),
+        Command::Share => infer_shared_game(&options),
+        Command::Wordlist => wordlist(&options),
+        Command::Play => play::play(&options),
+        Command::Help => {
+            println!("{}", cli::USAGE);
+            Ok(())
+        }
+    };
+
+    if let Err(err) = result {
+        if options.json {
+            println!("{}", json!({ "error": err.to_string() }));
+        } else {
+            eprintln!("{}", err);
+        }
+        process::exit(1);
+    }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
-struct Assignment {
-    letter: char,
-    position: usize,
-    status: LetterStatus,
+/// Words to guess from: the `--words` file if given, otherwise the built-in dictionary.
+fn load_words(options: &Options) -> Result<Vec<String>, Box<dyn Error>> {
+    let length = options.settings.word_length;
+
+    let dictionary = match &options.words {
+        Some(path) => words::parse(&fs::read_to_string(path)?, length),
+        None if length == 5 => words::dictionary(),
+        None => {
+            return Err(format!(
+                "The built-in word list only has 5 letter words, use --words for {} letter words.",
+                length
+            )
+            .into())
+        }
+    };
+
+    if dictionary.is_empty() {
+        return Err(format!("No {} letter words in the word list.", length).into());
+    }
+
+    Ok(dictionary)
 }
-#[derive(Clone, Copy, Debug, PartialEq)]
-enum LetterStatus {
-    Correct,
-    WrongSpot,
-    NotInWord,
+
+/// Words that can be answers. Every word in a `--words` file is treated as a possible answer.
+fn load_answers(options: &Options) -> Result<Vec<String>, Box<dyn Error>> {
+    match &options.words {
+        Some(_) => load_words(options),
+        None => {
+            load_words(options)?;
+            Ok(words::answers())
+        }
+    }
 }
 
-impl Puzzle {
-    fn setup() -> Puzzle {
-        println!(">parse");
+/// A puzzle set up from the command line options, with `args` applied as guesses. Guesses are given as `WORD:RESULTS`, e.g. `RUSTY:---.X`, or as bare results for whatever helple would have suggested at that point.
+fn puzzle(options: &Options, args: &[String]) -> Result<Puzzle, Box<dyn Error>> {
+    let mut puzzle = match &options.session {
+        Some(session) => session.to_puzzle(load_words(options)?)?,
+        None => Puzzle::with_settings(load_words(options)?, options.settings.clone()),
+    };
 
-        let mut dictionary: Vec<String> = Vec::new();
-        let input = File::open("./support/less-words").unwrap();
+    puzzle.exclude_answers(options.exclude.iter().cloned());
+    if let Some(path) = &options.history {
+        puzzle.exclude_answers(load_history(path, options.settings.word_length)?);
+    }
+    if let Some(date) = options.date {
+        // Only ever earlier answers, so solving the day's puzzle doesn't give it away.
+        puzzle.exclude_answers(daily::past_answers(date, &load_answers(options)?).to_vec());
+    }
 
-        for word_result in io::BufReader::new(input).lines() {
-            let word = word_result.unwrap();
-            let trimmed = word.trim();
-            if trimmed.len() != 5 {
-                continue;
-            }
+    for arg in args {
+        let (word, results) = match arg.split_once(':') {
+            Some((word, results)) => (word.to_uppercase(), results),
+            None => match puzzle.suggest_word(&HashSet::new()) {
+                Some(word) => (word, arg.as_str()),
+                None => {
+                    return Err(format!("No suggestion available to pair with <{}>.", arg).into())
+                }
+            },
+        };
 
-            dictionary.push(trimmed.to_string().to_uppercase());
+        // Lowercase `x` reads the same, as `cli::parse` already treats `--xx.` as results.
+        let statuses = parse_feedback(&results.to_uppercase())?;
+        if statuses.len() != word.len() {
+            return Err(format!("<{}> needs one result per letter of {}.", arg, word).into());
         }
-
-        Puzzle {
-            guesses: vec![],
-            assignments: vec![],
-            dictionary,
+        if word.len() != options.settings.word_length {
+            return Err(format!(
+                "<{}> should have {} letters.",
+                word, options.settings.word_length
+            )
+            .into());
         }
+
+        puzzle.assign_guess_results(word, statuses);
     }
 
-    fn solution(&self) -> Option<String> {
-        let mut correct_letters: Vec<char> = Vec::new();
+    Ok(puzzle)
+}
 
-        let mut correct_assignments: Vec<&Assignment> = self
-            .assignments
-            .iter()
-            .filter(|a| a.status == LetterStatus::Correct)
-            .clone()
-            .collect();
-        correct_assignments.sort_by(|a, b| a.position.cmp(&b.position));
+/// The text of a `--history` file. A missing file is an empty history.
+fn read_history(path: &str) -> Result<String, Box<dyn Error>> {
+    match fs::read_to_string(path) {
+        Ok(text) => Ok(text),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(String::new()),
+        Err(err) => Err(format!("{}: {}", path, err).into()),
+    }
+}
 
-        assert!(
-            correct_assignments.len() <= 5, // TODO: use real length depending on game
-            "Found {} 'correct' letters assigned. self.assignments={:?}",
-            correct_letters.len(),
-            self.assignments
-        );
+/// The answers recorded in a `--history` file, one per line.
+fn load_history(path: &str, length: usize) -> Result<Vec<String>, Box<dyn Error>> {
+    Ok(words::parse(&read_history(path)?, length))
+}
 
-        if correct_assignments.len() != 5 {
-            return None;
-        }
+/// Adds the puzzle's solution to the `--history` file, if there is one and the puzzle is solved.
+fn record_solution(options: &Options, puzzle: &Puzzle) -> Result<(), Box<dyn Error>> {
+    let (path, solution) = match (&options.history, puzzle.solution()) {
+        (Some(path), Some(solution)) => (path, solution),
+        _ => return Ok(()),
+    };
 
-        for a in &correct_assignments {
-            correct_letters.push(a.letter);
-        }
+    let history = read_history(path)?;
+    if words::parse(&history, solution.len()).contains(&solution) {
+        return Ok(());
+    }
 
-        Some(correct_letters.iter().collect())
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    // A file edited by hand may not end in a newline, which would glue the answer onto its last word.
+    if !history.is_empty() && !history.ends_with('\n') {
+        writeln!(file)?;
     }
+    writeln!(file, "{}", solution.to_lowercase())?;
 
-    fn is_permitted_word(&self, word: &String) -> bool {
-        let debug = false; // *word == "CRONK".to_string();
-        for a in &self.assignments {
-            if debug {
-                println!("Looking at rule {:?}", a);
-            }
-            let pass = match a.status {
-                LetterStatus::Correct => word.as_bytes()[a.position] == a.letter as u8,
-                LetterStatus::WrongSpot => {
-                    if debug {
-                        println!("   DEBUGGING LetterStatus::WrongSpot  ");
-                        println!(
-                            " - word.contains({})? {}",
-                            a.letter,
-                            word.contains(a.letter)
-                        );
-                        println!(" - word[{}] = {}", a.position, word.as_bytes()[a.position]);
-                        println!(" - a.letter={}", a.letter as u8);
-                    }
-                    word.contains(a.letter) && word.as_bytes()[a.position] != a.letter as u8
-                }
-                LetterStatus::NotInWord => {
-                    if debug {
-                        println!("Word contains {}? {}", a.letter, !word.contains(a.letter));
-                    }
-                    !word.contains(a.letter)
-                }
-            };
-
-            if !pass {
-                if debug {
-                    println!(
-                        "Word <{}> was rejected because it doesn't meet the requirements for {:?}",
-                        word, a
-                    );
-                }
-                return false;
-            }
-        }
+    Ok(())
+}
 
-        true
+/// Writes the puzzle to the `--save` file, if there is one.
+fn save(options: &Options, puzzle: &Puzzle) -> Result<(), Box<dyn Error>> {
+    if let Some(path) = &options.save {
+        fs::write(path, Session::from_puzzle(puzzle).to_json())?;
     }
 
-    fn suggest_word(&self) -> Option<String> {
-        #[derive(Debug)]
-        struct Suggestion {
-            score: usize,
-            word: String,
-        }
-
-        if self.assignments.len() == 0 {
-            return Some("RUSTY".to_string());
-        }
+    Ok(())
+}
 
-        let mut permitted: Vec<Suggestion> = Vec::new();
-        for word in &self.dictionary {
-            if self.is_permitted_word(&word) {
-                let score = score_for_potential_guess(word);
-                permitted.push(Suggestion {
-                    score,
-                    word: word.clone(),
-                });
-            }
-        }
+/// The name used for a letter status in JSON output.
+fn status_name(status: LetterStatus) -> &'static str {
+    match status {
+        LetterStatus::Correct => "correct",
+        LetterStatus::WrongSpot => "wrong_spot",
+        LetterStatus::NotInWord => "not_in_word",
+    }
+}
 
-        permitted.sort_by(|a, b| a.score.cmp(&b.score));
+fn guesses_json(guesses: &[(String, Vec<LetterStatus>)]) -> Value {
+    guesses
+        .iter()
+        .map(|(word, statuses)| json!({ "word": word, "results": format_feedback(statuses) }))
+        .collect()
+}
 
-        // println!("Suggestions sorted by score:\n{:?}", permitted);
+/// Prints the next suggestion, or the top few, for the guesses given as arguments.
+fn suggest(options: &Options) -> Result<(), Box<dyn Error>> {
+    let puzzle = puzzle(options, &options.args)?;
+    save(options, &puzzle)?;
 
-        match permitted.pop() {
-            Some(suggestion) => Some(suggestion.word.to_uppercase()),
-            None => None,
-        }
+    let suggestions = puzzle.suggestions(options.top);
+    if suggestions.is_empty() {
+        return Err("No suggestion available. 💥".into());
     }
 
-    #[allow(dead_code)]
-    fn assign_letter(&mut self, letter: char, position: usize, status: LetterStatus) {
-        let a = Assignment {
-            letter,
-            position,
-            status,
-        };
+    if options.json {
+        let suggestions: Vec<Value> = suggestions
+            .iter()
+            .map(|s| json!({ "word": s.word, "score": s.score }))
+            .collect();
+        println!(
+            "{}",
+            json!({ "suggestions": suggestions, "candidates": puzzle.candidates().len() })
+        );
+        return Ok(());
+    }
 
-        // Only add restrictions we aren't already aware of
-        if !self.assignments.contains(&a) {
-            self.assignments.push(a);
-        }
+    for suggestion in suggestions {
+        println!("{}", suggestion.word);
     }
 
-    fn assign_guess_results(&mut self, word: String, letter_statuses: Vec<LetterStatus>) {
-        assert!(
-            word.len() == letter_statuses.len(),
-            "Guessed word <{}> length must match letter statuses exactly: <{:?}>",
-            word,
-            letter_statuses
+    Ok(())
+}
+
+/// Lists the words that are still possible answers, one per line.
+fn filter(options: &Options) -> Result<(), Box<dyn Error>> {
+    let puzzle = puzzle(options, &options.args)?;
+    save(options, &puzzle)?;
+
+    let candidates = puzzle.candidates();
+    if options.json {
+        println!(
+            "{}",
+            json!({ "count": candidates.len(), "candidates": candidates })
         );
+        return Ok(());
+    }
 
-        for (i, byte) in word.as_bytes().iter().enumerate() {
-            let a = Assignment {
-                letter: *byte as char,
-                position: i,
-                status: letter_statuses[i],
-            };
+    for word in candidates {
+        println!("{}", word);
+    }
 
-            // Only add restrictions we aren't already aware of
-            if !self.assignments.contains(&a) {
-                self.assignments.push(a);
-            }
+    Ok(())
+}
+
+/// Plays helple against the given answers, or every answer when none are given.
+fn simulate(options: &Options) -> Result<(), Box<dyn Error>> {
+    const MAX_GUESSES: usize = 6;
+
+    let puzzle = puzzle(options, &[])?;
+    let answers = if options.args.is_empty() {
+        load_answers(options)?
+    } else {
+        options
+            .args
+            .iter()
+            .map(|answer| simulation::check_answer(answer, options.settings.word_length))
+            .collect::<Result<_, _>>()?
+    };
+
+    let mut distribution = [0usize; MAX_GUESSES];
+    let mut failed = 0;
+    let mut simulations: Vec<Value> = Vec::new();
+
+    for answer in &answers {
+        let simulation = simulation::simulate(puzzle.clone(), answer, MAX_GUESSES)?;
+        let guesses: Vec<&str> = simulation.guesses.iter().map(|(w, _)| w.as_str()).collect();
+
+        if simulation.solved {
+            distribution[guesses.len() - 1] += 1;
+        } else {
+            failed += 1;
         }
 
-        self.guesses.push((word.to_string(), letter_statuses));
+        if options.json {
+            simulations.push(json!({
+                "answer": simulation.answer,
+                "guesses": guesses_json(&simulation.guesses),
+                "solved": simulation.solved,
+            }));
+        } else if simulation.solved {
+            println!("{}: {} ({})", answer, guesses.join(" "), guesses.len());
+        } else {
+            println!("{}: {} (X)", answer, guesses.join(" "));
+        }
     }
 
-    fn assign_guess_from_cli(&mut self, word: String, input: &str) {
-        let mut letter_statuses: Vec<LetterStatus> = Vec::new();
+    let solved = answers.len() - failed;
+    let total_guesses: usize = distribution
+        .iter()
+        .enumerate()
+        .map(|(i, count)| (i + 1) * count)
+        .sum();
+    let average = if solved > 0 {
+        Some(total_guesses as f64 / solved as f64)
+    } else {
+        None
+    };
+
+    if options.json {
+        println!(
+            "{}",
+            json!({
+                "simulations": simulations,
+                "summary": {
+                    "total": answers.len(),
+                    "solved": solved,
+                    "failed": failed,
+                    "average_guesses": average,
+                    "distribution": distribution,
+                },
+            })
+        );
+        return Ok(());
+    }
 
-        for (i, grapheme) in input.graphemes(true).enumerate() {
-            let letter_status = match grapheme {
-                "X" => Some(LetterStatus::Correct),
-                "." => {
-                    // Partial hit
-                    Some(LetterStatus::WrongSpot)
-                }
-                "-" => Some(LetterStatus::NotInWord),
-                _ => None,
-            };
-
-            if let Some(status) = letter_status {
-                letter_statuses.push(status);
-            } else {
-                println!("Unexpected <{}> in input at character {}.", grapheme, i);
-                println!(
-                    r#"Expected format for puzzle results:
-`X` = direct hit (right letter in right position
-`.` = partial hit (right letter in wrong position)
-`-` = complete miss (letter not in word)"#
-                );
-                return;
-            }
+    if answers.len() > 1 {
+        println!();
+        println!("Solved {} of {}", solved, answers.len());
+        if let Some(average) = average {
+            println!("Average {:.3} guesses", average);
+        }
+        for (i, count) in distribution.iter().enumerate() {
+            println!("{}: {}", i + 1, count);
         }
+        println!("X: {}", failed);
+    }
+
+    Ok(())
+}
 
-        self.assign_guess_results(word, letter_statuses);
+/// Explains why a word is or isn't still a possible answer, e.g. `explain KNOLL SOARE:-.--- CLOUD:-.X--`.
+fn explain(options: &Options) -> Result<(), Box<dyn Error>> {
+    let word = match options.args.first() {
+        Some(word) => word.to_uppercase(),
+        None => return Err("Which word should I explain?".into()),
+    };
+
+    let puzzle = puzzle(options, &options.args[1..])?;
+    save(options, &puzzle)?;
+    let explanation = puzzle.explain(&word);
+
+    if options.json {
+        let mut output = match &explanation {
+            Explanation::Candidate => json!({ "status": "candidate" }),
+            Explanation::NotInWordList => json!({ "status": "not_in_word_list" }),
+            Explanation::Excluded => json!({ "status": "excluded" }),
+            Explanation::Eliminated {
+                guess_number,
+                guess,
+                assignment,
+            } => json!({
+                "status": "eliminated",
+                "guess_number": guess_number,
+                "guess": guess,
+                "rule": {
+                    "letter": assignment.letter.to_string(),
+                    "position": assignment.position + 1,
+                    "status": status_name(assignment.status),
+                },
+            }),
+        };
+        output["word"] = json!(word);
+        output["message"] = json!(explanation.to_string());
+        println!("{}", output);
+        return Ok(());
     }
+
+    println!("{}: {}", word, explanation);
+
+    Ok(())
 }
 
-/// When guessing a word, we want to "pin" and elimiate letters as fast as possible. Priorty is given to words that use the most unique letters. Further priority is given to words with the most vowels.
-fn score_for_potential_guess(word: &String) -> usize {
-    let mut score = 100;
+/// Scores each guess of a finished game for skill and luck.
+fn analyze(options: &Options) -> Result<(), Box<dyn Error>> {
+    let puzzle = puzzle(options, &options.args)?;
+    save(options, &puzzle)?;
+    let report = analysis::analyze(&puzzle, &load_answers(options)?)?;
 
-    for grapheme in word.graphemes(true) {
-        let count = word.matches(grapheme).collect::<String>().len();
-        if count > 1 {
-            score -= count * 2
-        }
+    if options.json {
+        let turns: Vec<Value> = report
+            .turns
+            .iter()
+            .map(|turn| {
+                json!({
+                    "guess": turn.guess,
+                    "results": format_feedback(&turn.feedback),
+                    "candidates_before": turn.candidates_before,
+                    "candidates_after": turn.candidates_after,
+                    "expected_bits": turn.expected_bits,
+                    "actual_bits": turn.actual_bits,
+                    "best_guess": turn.best_guess,
+                    "best_expected_bits": turn.best_expected_bits,
+                    "helple_guess": turn.helple_guess,
+                    "skill": turn.skill(),
+                    "luck": turn.luck(),
+                })
+            })
+            .collect();
+        println!(
+            "{}",
+            json!({ "turns": turns, "skill": report.skill(), "luck": report.luck() })
+        );
+        return Ok(());
+    }
 
-        let vowels = "AEIOUY"
-            .to_string()
-            .matches(grapheme)
-            .collect::<String>()
-            .len();
-        score += vowels;
+    for (i, turn) in report.turns.iter().enumerate() {
+        let tiles: String = turn.feedback.iter().map(|s| s.to_string()).collect();
+        println!("{}. {} {}", i + 1, turn.guess, tiles);
+        println!(
+            "   candidates: {} -> {}",
+            turn.candidates_before, turn.candidates_after
+        );
+        println!(
+            "   information: expected {:.2} bits, got {:.2} bits (best was {} with {:.2} bits)",
+            turn.expected_bits, turn.actual_bits, turn.best_guess, turn.best_expected_bits
+        );
+        if let Some(helple_guess) = &turn.helple_guess {
+            println!("   helple would have played {}", helple_guess);
+        }
+        println!(
+            "   skill {:.0}%, luck {:+.2}",
+            turn.skill() * 100.0,
+            turn.luck()
+        );
     }
 
-    score
+    println!(
+        "Overall: skill {:.0}%, luck {:+.2}",
+        report.skill() * 100.0,
+        report.luck()
+    );
+
+    Ok(())
 }
 
-impl fmt::Display for Puzzle {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let mut display = "".to_string();
+/// Reads a shared emoji grid from stdin and lists the answers (and guesses) that could have produced it.
+fn infer_shared_game(options: &Options) -> Result<(), Box<dyn Error>> {
+    const EXAMPLES: usize = 5;
 
-        for (_word, statuses) in &self.guesses {
-            for s in statuses {
-                display.push_str(format!("{}", s).as_str());
-            }
-            display.push_str("\n");
-        }
+    let mut text = String::new();
+    io::stdin().read_to_string(&mut text)?;
 
-        write!(f, "{}", display)
+    let game = share::parse_share(&text)?;
+    let dictionary = load_words(options)?;
+    let inferences = share::infer(&game.rows, &load_answers(options)?, &dictionary);
+    // An error in JSON mode too, so scripts can tell no match from a match.
+    if inferences.is_empty() {
+        return Err("No answer could have produced that grid. 🤔".into());
     }
+
+    if options.json {
+        let answers: Vec<Value> = inferences
+            .iter()
+            .map(|inference| {
+                let rows: Vec<Value> = inference
+                    .guesses
+                    .iter()
+                    .map(|guesses| {
+                        json!({
+                            "count": guesses.len(),
+                            "examples": guesses.iter().take(EXAMPLES).collect::<Vec<_>>(),
+                        })
+                    })
+                    .collect();
+
+                json!({
+                    "answer": inference.answer,
+                    "sequence_count": inference.sequence_count(),
+                    "rows": rows,
+                })
+            })
+            .collect();
+        let rows: Vec<String> = game.rows.iter().map(|row| format_feedback(row)).collect();
+
+        println!(
+            "{}",
+            json!({
+                "puzzle_number": game.puzzle_number,
+                "date": game.puzzle_number.map(|n| daily::date_for_puzzle(n).to_string()),
+                "solved_in": game.solved_in,
+                "hard_mode": game.hard_mode,
+                "rows": rows,
+                "answers": answers,
+            })
+        );
+        return Ok(());
+    }
+
+    if let Some(number) = game.puzzle_number {
+        println!(
+            "Wordle {} was played on {}.",
+            number,
+            daily::date_for_puzzle(number)
+        );
+    }
+    println!("{} possible answers:", inferences.len());
+    for inference in &inferences {
+        println!(
+            "{} ({} guess sequences)",
+            inference.answer,
+            inference.sequence_count()
+        );
+        for (row, guesses) in game.rows.iter().zip(&inference.guesses) {
+            let tiles: String = row.iter().map(|s| s.to_string()).collect();
+            let examples: Vec<&str> = guesses.iter().take(EXAMPLES).map(|g| g.as_str()).collect();
+            println!(
+                "  {} {} of {} words, e.g. {}",
+                tiles,
+                guesses.len(),
+                dictionary.len(),
+                examples.join(", ")
+            );
+        }
+    }
+
+    Ok(())
 }
 
-impl fmt::Display for LetterStatus {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let s = match self {
-            &LetterStatus::Correct => "🟩",
-            &LetterStatus::WrongSpot => "🟨",
-            &LetterStatus::NotInWord => "⬜",
-        };
+/// Prints the word list in use, one word per line.
+fn wordlist(options: &Options) -> Result<(), Box<dyn Error>> {
+    let words = load_words(options)?;
+
+    if options.json {
+        println!("{}", json!({ "words": words }));
+        return Ok(());
+    }
 
-        write!(f, "{}", s)
+    for word in words {
+        println!("{}", word);
     }
+
+    Ok(())
 }
 
-fn main() -> Result<(), std::io::Error> {
-    let mut puzzle = Puzzle::setup();
-    let mut buffer = String::new();
-    let stdin = io::stdin();
+#[cfg(test)]
+mod test {
+    use super::*;
 
-    loop {
-        let suggestion = puzzle.suggest_word();
+    fn options(args: &[&str]) -> Options {
+        cli::parse(args.iter().map(|a| a.to_string())).unwrap()
+    }
 
-        match suggestion {
-            Some(word) => {
-                println!("Go type <{:?}> into the puzzle. What was the result?", word);
+    /// A path in the temp directory for `name`, with nothing there yet.
+    fn temp_path(name: &str) -> String {
+        let path = std::env::temp_dir().join(format!("helple-{}-{}", process::id(), name));
+        let _ = fs::remove_file(&path);
+        path.to_str().unwrap().to_string()
+    }
 
-                stdin.read_line(&mut buffer)?;
-                let input = buffer.trim().clone();
+    fn solved(answer: &str) -> Puzzle {
+        let mut puzzle = Puzzle::setup();
+        puzzle.assign_guess_results(answer.to_string(), vec![LetterStatus::Correct; 5]);
+        puzzle
+    }
 
-                puzzle.assign_guess_from_cli(word, input);
-                println!("{}", puzzle);
-                buffer.clear();
-            }
-            None => {
-                println!("No suggestion available. 💥");
-                break;
-            }
-        }
+    #[test]
+    fn records_solutions_in_the_history() {
+        let path = temp_path("history");
+        let options = options(&["solve", "--history", &path]);
+        assert!(load_history(&path, 5).unwrap().is_empty());
 
-        if let Some(solution) = puzzle.solution() {
-            println!("Puzzle solved using {}! 🙌 Share your score. 😘", solution);
-            break;
-        }
+        record_solution(&options, &solved("CRANE")).unwrap();
+        record_solution(&options, &solved("SLATE")).unwrap();
+        record_solution(&options, &solved("CRANE")).unwrap();
+        assert_eq!(vec!["CRANE", "SLATE"], load_history(&path, 5).unwrap());
+
+        fs::remove_file(&path).unwrap();
     }
 
-    Ok(())
+    #[test]
+    fn records_after_a_last_line_without_a_newline() {
+        let path = temp_path("history-no-newline");
+        fs::write(&path, "crane").unwrap();
+        let options = options(&["solve", "--history", &path]);
+
+        record_solution(&options, &solved("SLATE")).unwrap();
+        assert_eq!("crane\nslate\n", fs::read_to_string(&path).unwrap());
+        assert_eq!(vec!["CRANE", "SLATE"], load_history(&path, 5).unwrap());
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn applies_bare_and_lowercase_results() {
+        let options = options(&["suggest", "--xXX", "toney:x-x-x"]);
+        let puzzle = puzzle(&options, &options.args).unwrap();
+
+        let expected = vec![
+            ("RUSTY".to_string(), parse_feedback("--XXX").unwrap()),
+            ("TONEY".to_string(), parse_feedback("X-X-X").unwrap()),
+        ];
+        assert_eq!(expected, puzzle.guesses());
+    }
 }
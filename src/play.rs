@@ -0,0 +1,110 @@
+use crate::cli::Options;
+use helple::game::{self, Game, MAX_GUESSES};
+use helple::{daily, format_feedback};
+use serde_json::json;
+use std::collections::hash_map::RandomState;
+use std::error::Error;
+use std::hash::{BuildHasher, Hasher};
+use std::io;
+
+/// A seed for a game nobody has asked for by number. `RandomState` is seeded from the OS, which saves pulling in a random number crate for one number.
+fn random_seed() -> u64 {
+    RandomState::new().build_hasher().finish()
+}
+
+/// Plays a game against a hidden answer read from stdin, one guess per line.
+pub fn play(options: &Options) -> Result<(), Box<dyn Error>> {
+    let answers = crate::load_answers(options)?;
+
+    let (answer, puzzle_number, seed) = match options.date {
+        Some(date) => match daily::scheduled_answer(date, &answers) {
+            Some(answer) => (answer, daily::puzzle_number(date), None),
+            None => return Err(format!("No puzzle is scheduled for {}.", date).into()),
+        },
+        None => {
+            let seed = options.seed.unwrap_or_else(random_seed);
+            let answer = game::answer_for_seed(seed, &answers).ok_or("No answers to play.")?;
+            (answer, None, Some(seed))
+        }
+    };
+
+    let mut game = Game::new(
+        answer,
+        &crate::load_words(options)?,
+        options.settings.hard_mode,
+    );
+
+    if options.json {
+        println!(
+            "{}",
+            json!({
+                "seed": seed,
+                "puzzle_number": puzzle_number,
+                "length": answer.len(),
+                "max_guesses": MAX_GUESSES,
+            })
+        );
+    } else {
+        match (puzzle_number, seed) {
+            (Some(number), _) => println!("Wordle {}", number),
+            (_, Some(seed)) => println!("Game {} (replay it with --seed {})", seed, seed),
+            _ => {}
+        }
+        println!(
+            "Guess the {} letter word in {} tries.",
+            answer.len(),
+            MAX_GUESSES
+        );
+    }
+
+    let mut buffer = String::new();
+    let stdin = io::stdin();
+
+    while !game.is_over() {
+        buffer.clear();
+        if stdin.read_line(&mut buffer)? == 0 {
+            break;
+        }
+
+        let result = game.guess(&buffer).map(|statuses| statuses.to_vec());
+        let remaining = MAX_GUESSES - game.guesses().len();
+
+        match (result, options.json) {
+            (Ok(statuses), true) => println!(
+                "{}",
+                json!({
+                    "guess": game.guesses().last().unwrap().0,
+                    "results": format_feedback(&statuses),
+                    "remaining": remaining,
+                })
+            ),
+            (Ok(statuses), false) => {
+                let (word, _) = game.guesses().last().unwrap();
+                let tiles: String = statuses.iter().map(|s| s.to_string()).collect();
+                println!("{} {}", word, tiles);
+            }
+            (Err(err), true) => println!("{}", json!({ "error": err.to_string() })),
+            (Err(err), false) => println!("{}. Try again.", err),
+        }
+    }
+
+    let share = game.shared(puzzle_number).to_string();
+
+    if options.json {
+        println!(
+            "{}",
+            json!({ "won": game.is_won(), "answer": game.answer(), "share": share })
+        );
+        return Ok(());
+    }
+
+    if game.is_won() {
+        println!("Solved in {}! 🙌", game.guesses().len());
+    } else {
+        println!("The answer was {}.", game.answer());
+    }
+    println!();
+    println!("{}", share);
+
+    Ok(())
+}
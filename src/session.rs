@@ -0,0 +1,240 @@
+use crate::{format_feedback, parse_feedback, FeedbackError, Puzzle, Settings, Suggestion};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Bumped whenever a change to `Session` would stop older versions of helple from reading it correctly.
+pub const SESSION_VERSION: u32 = 1;
+
+/// A puzzle in progress, as saved by the CLI and read from the game board by the extension. The extension only sends `guesses`; everything else falls back to its default.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct Session {
+    #[serde(default = "current_version")]
+    pub version: u32,
+    #[serde(default)]
+    pub settings: Settings,
+    pub guesses: Vec<SessionGuess>,
+}
+
+/// A guess with its results typed as on the command line, e.g. `---.X`.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct SessionGuess {
+    pub word: String,
+    pub results: String,
+}
+
+/// What the solver makes of a session, as returned to JavaScript. `error` is set instead of the rest when the session can't be read.
+#[derive(Serialize, Debug, Default, PartialEq)]
+pub struct Report {
+    pub suggestions: Vec<Suggestion>,
+    pub candidate_count: usize,
+    pub remaining: Vec<String>,
+    pub error: Option<String>,
+}
+
+impl Report {
+    pub fn error(message: String) -> Report {
+        Report {
+            error: Some(message),
+            ..Report::default()
+        }
+    }
+}
+
+fn current_version() -> u32 {
+    SESSION_VERSION
+}
+
+#[derive(Debug)]
+pub enum SessionError {
+    Json(serde_json::Error),
+    UnsupportedVersion(u32),
+    Results { guess: usize, error: FeedbackError },
+    Length { guess: usize },
+}
+
+impl fmt::Display for SessionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SessionError::Json(err) => write!(f, "Couldn't read session: {}", err),
+            SessionError::UnsupportedVersion(version) => write!(
+                f,
+                "Session version {} is newer than this version of helple supports ({})",
+                version, SESSION_VERSION
+            ),
+            SessionError::Results { guess, error } => {
+                write!(f, "Guess {} in session: {}", guess, error)
+            }
+            SessionError::Length { guess } => write!(
+                f,
+                "Guess {} in session must have the session's word length and one result per letter",
+                guess
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SessionError {}
+
+impl From<serde_json::Error> for SessionError {
+    fn from(err: serde_json::Error) -> Self {
+        SessionError::Json(err)
+    }
+}
+
+impl Session {
+    pub fn from_puzzle(puzzle: &Puzzle) -> Session {
+        Session {
+            version: SESSION_VERSION,
+            settings: puzzle.settings().clone(),
+            guesses: puzzle
+                .guesses()
+                .iter()
+                .map(|(word, statuses)| SessionGuess {
+                    word: word.clone(),
+                    results: format_feedback(statuses),
+                })
+                .collect(),
+        }
+    }
+
+    /// Rebuilds the puzzle, guessing from `dictionary`.
+    pub fn to_puzzle(&self, dictionary: Vec<String>) -> Result<Puzzle, SessionError> {
+        let mut puzzle = Puzzle::with_settings(dictionary, self.settings.clone());
+
+        for (i, guess) in self.guesses.iter().enumerate() {
+            let statuses =
+                parse_feedback(&guess.results).map_err(|error| SessionError::Results {
+                    guess: i + 1,
+                    error,
+                })?;
+
+            if statuses.len() != guess.word.len() || guess.word.len() != self.settings.word_length {
+                return Err(SessionError::Length { guess: i + 1 });
+            }
+
+            puzzle.assign_guess_results(guess.word.to_uppercase(), statuses);
+        }
+
+        Ok(puzzle)
+    }
+
+    /// The top `count` suggestions for this session and the words still left, guessing from `dictionary`.
+    pub fn report(&self, dictionary: Vec<String>, count: usize) -> Report {
+        match self.to_puzzle(dictionary) {
+            Ok(puzzle) => {
+                let remaining = puzzle.candidates();
+                Report {
+                    suggestions: puzzle.suggestions(count),
+                    candidate_count: remaining.len(),
+                    remaining,
+                    error: None,
+                }
+            }
+            Err(err) => Report::error(err.to_string()),
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("Sessions always serialize")
+    }
+
+    pub fn from_json(json: &str) -> Result<Session, SessionError> {
+        let session: Session = serde_json::from_str(json)?;
+
+        if session.version > SESSION_VERSION {
+            return Err(SessionError::UnsupportedVersion(session.version));
+        }
+
+        Ok(session)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Strategy;
+    use std::collections::HashSet;
+
+    #[test]
+    fn round_trips_through_json() {
+        let settings = Settings {
+            hard_mode: true,
+            strategy: Strategy::Entropy,
+            ..Settings::default()
+        };
+        let mut puzzle = Puzzle::with_settings(crate::words::dictionary(), settings);
+        puzzle.assign_guess_from_cli("RUSTY".to_string(), "---.X");
+        puzzle.assign_guess_from_cli("TONEY".to_string(), "X-X-X");
+
+        let session = Session::from_json(&Session::from_puzzle(&puzzle).to_json()).unwrap();
+        assert_eq!(Session::from_puzzle(&puzzle), session);
+
+        let resumed = session.to_puzzle(crate::words::dictionary()).unwrap();
+        assert_eq!(puzzle.guesses(), resumed.guesses());
+        assert_eq!(puzzle.settings(), resumed.settings());
+        assert_eq!(
+            puzzle.suggest_word(&HashSet::new()),
+            resumed.suggest_word(&HashSet::new())
+        );
+    }
+
+    #[test]
+    fn rejects_words_of_the_wrong_length() {
+        let session =
+            Session::from_json(r#"{"guesses":[{"word":"RUSTYS","results":"---.X-"}]}"#).unwrap();
+
+        assert!(matches!(
+            session.to_puzzle(crate::words::dictionary()),
+            Err(SessionError::Length { guess: 1 })
+        ));
+    }
+
+    #[test]
+    fn reads_extension_board_state() {
+        let session =
+            Session::from_json(r#"{"guesses":[{"word":"rusty","results":"---.X"}]}"#).unwrap();
+
+        assert_eq!(SESSION_VERSION, session.version);
+        assert_eq!(Settings::default(), session.settings);
+
+        let puzzle = session.to_puzzle(crate::words::dictionary()).unwrap();
+        assert_eq!("RUSTY", puzzle.guesses()[0].0);
+    }
+
+    #[test]
+    fn reports_suggestions_and_remaining_words() {
+        let session = Session::from_json(
+            r#"{"guesses":[{"word":"rusty","results":"---.X"},{"word":"toney","results":"X-X-X"}]}"#,
+        )
+        .unwrap();
+
+        let report = session.report(crate::words::dictionary(), 2);
+        assert_eq!(None, report.error);
+        assert_eq!(3, report.candidate_count);
+        assert_eq!(vec!["TANGY", "TANKY", "TINNY"], report.remaining);
+        assert_eq!(2, report.suggestions.len());
+
+        let session = Session::from_json(r#"{"guesses":[{"word":"rusty","results":"??"}]}"#);
+        let report = session.unwrap().report(crate::words::dictionary(), 1);
+        assert!(report.error.is_some());
+        assert!(report.suggestions.is_empty());
+    }
+
+    #[test]
+    fn rejects_bad_sessions() {
+        assert!(matches!(
+            Session::from_json(r#"{"version":99,"guesses":[]}"#),
+            Err(SessionError::UnsupportedVersion(99))
+        ));
+        assert!(matches!(
+            Session::from_json(r#"{"guesses":[{"word":"RUSTY"}]}"#),
+            Err(SessionError::Json(_))
+        ));
+
+        let session = Session::from_json(r#"{"guesses":[{"word":"RUSTY","results":"--"}]}"#);
+        assert!(matches!(
+            session.unwrap().to_puzzle(vec![]),
+            Err(SessionError::Length { guess: 1 })
+        ));
+    }
+}
@@ -0,0 +1,354 @@
+use crate::game::MAX_GUESSES;
+use crate::{evaluate_guess_into, LetterStatus};
+use std::fmt;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// A game as players share it once they are done, e.g.
+///
+/// ```text
+/// Wordle 210 3/6
+///
+/// ⬜🟨⬜⬜⬜
+/// 🟩🟩⬜🟨⬜
+/// 🟩🟩🟩🟩🟩
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct SharedGame {
+    pub puzzle_number: Option<u32>,
+    /// Number of guesses it took, `None` for a failed game (`X/6`) or when there was no header line.
+    pub solved_in: Option<usize>,
+    pub hard_mode: bool,
+    pub rows: Vec<Vec<LetterStatus>>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ShareError {
+    NoRows,
+    UnexpectedTile {
+        line: usize,
+        tile: String,
+    },
+    RowLength {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+    RowCount {
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl fmt::Display for ShareError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ShareError::NoRows => write!(f, "No rows of tiles found"),
+            ShareError::UnexpectedTile { line, tile } => {
+                write!(f, "Unexpected <{}> in tiles on line {}", tile, line)
+            }
+            ShareError::RowLength {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Row on line {} has {} tiles, expected {}",
+                line, found, expected
+            ),
+            ShareError::RowCount { expected, found } => write!(
+                f,
+                "Header reports {} guesses but the grid has {} rows",
+                expected, found
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ShareError {}
+
+impl fmt::Display for SharedGame {
+    /// Writes the game the way the share button does, e.g. `Wordle 210 3/6*` followed by the grid. Games without a puzzle number are headed `helple` instead.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.puzzle_number {
+            Some(number) => write!(f, "Wordle {} ", number)?,
+            None => write!(f, "helple ")?,
+        }
+        match self.solved_in {
+            Some(solved_in) => write!(f, "{}/{}", solved_in, MAX_GUESSES)?,
+            None => write!(f, "X/{}", MAX_GUESSES)?,
+        }
+        if self.hard_mode {
+            write!(f, "*")?;
+        }
+        writeln!(f)?;
+
+        for row in &self.rows {
+            writeln!(f)?;
+            for status in row {
+                write!(f, "{}", status)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Maps a single share tile to the status it represents. Covers the light (⬜) and dark (⬛) themes as well as the high contrast colours (🟧 correct, 🟦 wrong spot).
+fn tile_status(tile: &str) -> Option<LetterStatus> {
+    match tile.trim_end_matches('\u{fe0f}') {
+        "🟩" | "🟧" => Some(LetterStatus::Correct),
+        "🟨" | "🟦" => Some(LetterStatus::WrongSpot),
+        "⬜" | "⬛" => Some(LetterStatus::NotInWord),
+        _ => None,
+    }
+}
+
+/// Reads the `Wordle 210 3/6*` header line. Returns `None` for anything else.
+fn parse_header(line: &str) -> Option<(Option<u32>, Option<usize>, bool)> {
+    let mut parts = line.split_whitespace();
+    if parts.next() != Some("Wordle") {
+        return None;
+    }
+
+    let puzzle_number = parts
+        .next()
+        .and_then(|n| n.trim_start_matches('#').replace(',', "").parse().ok());
+
+    let (score, hard_mode) = match parts.next() {
+        Some(score) => match score.strip_suffix('*') {
+            Some(score) => (score, true),
+            None => (score, false),
+        },
+        None => return Some((puzzle_number, None, false)),
+    };
+
+    let solved_in = score.split('/').next().and_then(|n| n.parse().ok());
+
+    Some((puzzle_number, solved_in, hard_mode))
+}
+
+/// Parses the text copied from the game's share button. Lines that are neither the header nor tiles (blank lines, links, commentary) are ignored.
+pub fn parse_share(text: &str) -> Result<SharedGame, ShareError> {
+    let mut game = SharedGame {
+        puzzle_number: None,
+        solved_in: None,
+        hard_mode: false,
+        rows: vec![],
+    };
+    let mut header_found = false;
+
+    for (i, line) in text.lines().enumerate() {
+        let line_number = i + 1;
+
+        if !header_found {
+            if let Some((puzzle_number, solved_in, hard_mode)) = parse_header(line) {
+                header_found = true;
+                game.puzzle_number = puzzle_number;
+                game.solved_in = solved_in;
+                game.hard_mode = hard_mode;
+                continue;
+            }
+        }
+
+        let tiles: Vec<&str> = line
+            .graphemes(true)
+            .filter(|g| !g.trim().is_empty())
+            .collect();
+
+        match tiles.first() {
+            Some(first) if tile_status(first).is_some() => {}
+            _ => continue,
+        }
+
+        let mut row: Vec<LetterStatus> = Vec::new();
+        for tile in tiles {
+            match tile_status(tile) {
+                Some(status) => row.push(status),
+                None => {
+                    return Err(ShareError::UnexpectedTile {
+                        line: line_number,
+                        tile: tile.to_string(),
+                    })
+                }
+            }
+        }
+
+        if let Some(first_row) = game.rows.first() {
+            if first_row.len() != row.len() {
+                return Err(ShareError::RowLength {
+                    line: line_number,
+                    expected: first_row.len(),
+                    found: row.len(),
+                });
+            }
+        }
+
+        game.rows.push(row);
+    }
+
+    if game.rows.is_empty() {
+        return Err(ShareError::NoRows);
+    }
+
+    if let Some(expected) = game.solved_in {
+        if expected != game.rows.len() {
+            return Err(ShareError::RowCount {
+                expected,
+                found: game.rows.len(),
+            });
+        }
+    }
+
+    Ok(game)
+}
+
+/// An answer that could have produced a shared grid, along with the words that could have been played on each row.
+#[derive(Debug, PartialEq)]
+pub struct Inference {
+    pub answer: String,
+    pub guesses: Vec<Vec<String>>,
+}
+
+impl Inference {
+    /// How many distinct guess sequences lead to this grid for this answer.
+    pub fn sequence_count(&self) -> usize {
+        self.guesses
+            .iter()
+            .fold(1usize, |total, row| total.saturating_mul(row.len()))
+    }
+}
+
+/// Works backwards from a grid to every answer in `answers` that at least one sequence of words from `guesses` could have produced. Hard mode is not taken into account, so every row is treated independently.
+pub fn infer(rows: &[Vec<LetterStatus>], answers: &[String], guesses: &[String]) -> Vec<Inference> {
+    let width = match rows.first() {
+        Some(row) => row.len(),
+        None => return vec![],
+    };
+
+    let mut inferences: Vec<Inference> = Vec::new();
+    let mut feedback: Vec<LetterStatus> = Vec::with_capacity(width);
+
+    for answer in answers {
+        if answer.len() != width {
+            continue;
+        }
+
+        let mut played: Vec<Vec<String>> = vec![vec![]; rows.len()];
+        for guess in guesses {
+            if guess.len() != width {
+                continue;
+            }
+
+            evaluate_guess_into(guess.as_bytes(), answer.as_bytes(), &mut feedback);
+            for (i, row) in rows.iter().enumerate() {
+                if feedback == *row {
+                    played[i].push(guess.clone());
+                }
+            }
+        }
+
+        if played.iter().any(|words| words.is_empty()) {
+            continue;
+        }
+
+        inferences.push(Inference {
+            answer: answer.clone(),
+            guesses: played,
+        });
+    }
+
+    inferences
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use LetterStatus::*;
+
+    fn words(list: &[&str]) -> Vec<String> {
+        list.iter().map(|w| w.to_string()).collect()
+    }
+
+    #[test]
+    fn parses_light_dark_and_high_contrast() {
+        let light = "Wordle 210 3/6\n\n⬜🟨⬜⬜⬜\n🟩🟩⬜🟨⬜\n🟩🟩🟩🟩🟩\n";
+        let dark = "Wordle 210 3/6\n\n⬛🟨⬛⬛⬛\n🟩🟩⬛🟨⬛\n🟩🟩🟩🟩🟩\n";
+        let contrast = "Wordle 210 3/6\n\n⬜️🟦⬜️⬜️⬜️\n🟧🟧⬜️🟦⬜️\n🟧🟧🟧🟧🟧\n";
+
+        let expected = SharedGame {
+            puzzle_number: Some(210),
+            solved_in: Some(3),
+            hard_mode: false,
+            rows: vec![
+                vec![NotInWord, WrongSpot, NotInWord, NotInWord, NotInWord],
+                vec![Correct, Correct, NotInWord, WrongSpot, NotInWord],
+                vec![Correct; 5],
+            ],
+        };
+
+        assert_eq!(Ok(expected.clone()), parse_share(light));
+        assert_eq!(Ok(expected.clone()), parse_share(dark));
+        assert_eq!(Ok(expected), parse_share(contrast));
+    }
+
+    #[test]
+    fn parses_failed_hard_mode_header() {
+        let text = "Wordle 1,234 X/6*\n⬜⬜⬜⬜⬜\n⬜⬜⬜⬜⬜\n⬜⬜⬜⬜⬜\n⬜⬜⬜⬜⬜\n⬜⬜⬜⬜⬜\n⬜⬜⬜⬜⬜";
+        let game = parse_share(text).unwrap();
+
+        assert_eq!(Some(1234), game.puzzle_number);
+        assert_eq!(None, game.solved_in);
+        assert!(game.hard_mode);
+        assert_eq!(6, game.rows.len());
+    }
+
+    #[test]
+    fn rejects_malformed_grids() {
+        assert_eq!(Err(ShareError::NoRows), parse_share("Wordle 210 3/6"));
+        assert_eq!(
+            Err(ShareError::RowLength {
+                line: 2,
+                expected: 5,
+                found: 4
+            }),
+            parse_share("⬜⬜⬜⬜⬜\n⬜⬜⬜⬜")
+        );
+        assert_eq!(
+            Err(ShareError::UnexpectedTile {
+                line: 1,
+                tile: "🟥".to_string()
+            }),
+            parse_share("⬜⬜🟥⬜⬜")
+        );
+        assert_eq!(
+            Err(ShareError::RowCount {
+                expected: 3,
+                found: 1
+            }),
+            parse_share("Wordle 210 3/6\n🟩🟩🟩🟩🟩")
+        );
+    }
+
+    #[test]
+    fn infers_answers_from_grid() {
+        let answers = words(&["KNOLL", "TANGY", "ABBEY"]);
+        let guesses = words(&["KNOLL", "TANGY", "ABBEY", "RUSTY", "OVOLI"]);
+
+        // RUSTY, OVOLI, KNOLL
+        let game = parse_share("⬜⬜⬜⬜⬜\n⬜⬜🟩🟩⬜\n🟩🟩🟩🟩🟩").unwrap();
+        let inferences = infer(&game.rows, &answers, &guesses);
+
+        assert_eq!(
+            vec![Inference {
+                answer: "KNOLL".to_string(),
+                guesses: vec![
+                    words(&["ABBEY", "RUSTY"]),
+                    words(&["OVOLI"]),
+                    words(&["KNOLL"]),
+                ],
+            }],
+            inferences
+        );
+        assert_eq!(2, inferences[0].sequence_count());
+    }
+}
@@ -0,0 +1,142 @@
+use crate::{evaluate_guess, LetterStatus, Puzzle};
+use std::collections::HashSet;
+use std::fmt;
+
+/// The guesses a puzzle made against a known answer.
+#[derive(Debug, PartialEq)]
+pub struct Simulation {
+    pub answer: String,
+    pub guesses: Vec<(String, Vec<LetterStatus>)>,
+    pub solved: bool,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum SimulationError {
+    /// The answer isn't a word of the puzzle's length.
+    InvalidAnswer { answer: String, length: usize },
+}
+
+impl fmt::Display for SimulationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SimulationError::InvalidAnswer { answer, length } => {
+                write!(f, "<{}> isn't a word of {} letters", answer, length)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SimulationError {}
+
+/// Uppercases `answer`, checking it's a word of `length` letters.
+pub fn check_answer(answer: &str, length: usize) -> Result<String, SimulationError> {
+    if answer.len() != length || !answer.chars().all(|c| c.is_ascii_alphabetic()) {
+        return Err(SimulationError::InvalidAnswer {
+            answer: answer.to_string(),
+            length,
+        });
+    }
+
+    Ok(answer.to_uppercase())
+}
+
+/// Plays `puzzle`'s suggestions against `answer` until it is solved, the suggestions run out, or `max_guesses` have been made.
+pub fn simulate(
+    mut puzzle: Puzzle,
+    answer: &str,
+    max_guesses: usize,
+) -> Result<Simulation, SimulationError> {
+    let answer = check_answer(answer, puzzle.settings().word_length)?;
+    let mut guesses = Vec::new();
+
+    while guesses.len() < max_guesses {
+        let word = match puzzle.suggest_word(&HashSet::new()) {
+            Some(word) => word,
+            None => break,
+        };
+
+        let statuses = evaluate_guess(&word, &answer);
+        guesses.push((word.clone(), statuses.clone()));
+
+        if word == answer {
+            return Ok(Simulation {
+                answer,
+                guesses,
+                solved: true,
+            });
+        }
+
+        puzzle.assign_guess_results(word, statuses);
+    }
+
+    Ok(Simulation {
+        answer,
+        guesses,
+        solved: false,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Settings, Strategy};
+
+    #[test]
+    fn solves_knoll() {
+        let simulation = simulate(Puzzle::setup(), "knoll", 6).unwrap();
+
+        assert!(simulation.solved);
+        assert_eq!("RUSTY", simulation.guesses[0].0);
+        assert_eq!("KNOLL", simulation.guesses.last().unwrap().0);
+    }
+
+    #[test]
+    fn entropy_strategy_solves_small_list() {
+        let dictionary: Vec<String> = ["CIGAR", "REBUT", "SISSY", "HUMPH", "AWAKE", "BLUSH"]
+            .iter()
+            .map(|w| w.to_string())
+            .collect();
+        let settings = Settings {
+            strategy: Strategy::Entropy,
+            opening: None,
+            ..Settings::default()
+        };
+
+        for answer in &dictionary {
+            let puzzle = Puzzle::with_settings(dictionary.clone(), settings.clone());
+            let simulation = simulate(puzzle, answer, 6).unwrap();
+
+            assert!(simulation.solved, "Failed to solve {}", answer);
+            assert!(simulation.guesses.len() <= 3);
+        }
+    }
+
+    #[test]
+    fn never_guesses_a_word_twice() {
+        // Repeated letters used to leave a wrong guess permitted, so it was played again and again.
+        for answer in ["CLICK", "CONIC", "SPELL", "RAZOR", "SHELL"] {
+            let simulation = simulate(Puzzle::setup(), answer, 6).unwrap();
+            let guessed: HashSet<&String> = simulation.guesses.iter().map(|(w, _)| w).collect();
+
+            assert_eq!(
+                simulation.guesses.len(),
+                guessed.len(),
+                "Repeated a guess for {}: {:?}",
+                answer,
+                simulation.guesses
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_answers_that_arent_words_of_the_right_length() {
+        assert_eq!(
+            Err(SimulationError::InvalidAnswer {
+                answer: "abc".to_string(),
+                length: 5
+            }),
+            simulate(Puzzle::setup(), "abc", 6)
+        );
+        assert!(simulate(Puzzle::setup(), "12345", 6).is_err());
+    }
+}
@@ -0,0 +1,351 @@
+use crate::board::{
+    click, document, session_from_rows, shadow_root, BoardAdapter, BoardError, Key, Query, Row,
+};
+use web_sys::{DocumentFragment, Element};
+
+/// A site that hosts a Wordle game helple knows how to play.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Site {
+    /// The New York Times, where the game lives now.
+    Nyt,
+    /// powerlanguage.co.uk, where the game started.
+    Original,
+    /// Quordle, four boards at once. helple plays the first one that isn't solved yet.
+    Quordle,
+    WordleUnlimited,
+}
+
+impl Site {
+    /// The site a page belongs to, judged by its URL.
+    pub fn from_url(url: &str) -> Option<Site> {
+        let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+        let (host, path) = rest.split_once('/').unwrap_or((rest, ""));
+        let host = host.to_lowercase();
+        let is = |domain: &str| host == domain || host.ends_with(&format!(".{}", domain));
+
+        if is("nytimes.com") && path.starts_with("games/wordle") {
+            Some(Site::Nyt)
+        } else if is("powerlanguage.co.uk") {
+            Some(Site::Original)
+        } else if is("quordle.com")
+            || (is("merriam-webster.com") && path.starts_with("games/quordle"))
+        {
+            Some(Site::Quordle)
+        } else if is("wordleunlimited.org") {
+            Some(Site::WordleUnlimited)
+        } else {
+            None
+        }
+    }
+
+    /// The adapter for this site's board on the current page.
+    pub fn board(&self) -> Result<Box<dyn BoardAdapter>, BoardError> {
+        Ok(match self {
+            Site::Nyt => Box::new(NytBoard),
+            Site::Original => Box::new(OriginalBoard::find()?),
+            Site::Quordle => Box::new(QuordleBoard),
+            Site::WordleUnlimited => Box::new(UnlimitedBoard),
+        })
+    }
+}
+
+/// The adapter for the page helple is running in.
+pub fn find_board() -> Result<Box<dyn BoardAdapter>, BoardError> {
+    let url = web_sys::window()
+        .and_then(|window| window.location().href().ok())
+        .ok_or_else(|| BoardError::Missing("the page's URL".to_string()))?;
+
+    match Site::from_url(&url) {
+        Some(site) => site.board(),
+        None => Err(BoardError::UnsupportedSite(url)),
+    }
+}
+
+/// The original game's board: `game-row`s of `game-tile`s and a `game-keyboard`, each behind a shadow root inside `game-app`.
+pub struct OriginalBoard {
+    app: DocumentFragment,
+}
+
+impl OriginalBoard {
+    pub fn find() -> Result<OriginalBoard, BoardError> {
+        let app = document()?.select("game-app")?;
+
+        Ok(OriginalBoard {
+            app: shadow_root(&app, "game-app")?,
+        })
+    }
+}
+
+impl BoardAdapter for OriginalBoard {
+    fn read_rows(&self) -> Result<Vec<Row>, BoardError> {
+        let mut rows: Vec<Row> = Vec::new();
+
+        for row in self.app.select_all("game-row") {
+            let word = row.get_attribute("letters").unwrap_or_default();
+            let tiles = shadow_root(&row, "game-row")?.select_all("game-tile");
+            let evaluations = tiles
+                .iter()
+                .map(|tile| tile.get_attribute("evaluation"))
+                .collect();
+
+            rows.push((word.to_uppercase(), evaluations));
+        }
+
+        Ok(rows)
+    }
+
+    fn press_key(&self, key: Key) -> Result<(), BoardError> {
+        let keyboard = shadow_root(&self.app.select("game-keyboard")?, "game-keyboard")?;
+        let name = data_key(key);
+
+        click(keyboard.select(&format!("[data-key='{}']", name))?, &name)
+    }
+}
+
+/// The `data-key` of a key on the original and NYT keyboards.
+fn data_key(key: Key) -> String {
+    match key {
+        Key::Letter(letter) => letter.to_ascii_lowercase().to_string(),
+        Key::Enter => "↵".to_string(),
+        Key::Backspace => "←".to_string(),
+    }
+}
+
+/// Tiles that haven't been submitted are `empty`, or `tbd` once a letter is typed.
+fn nyt_evaluation(state: &str) -> Option<String> {
+    match state {
+        "empty" | "tbd" => None,
+        state => Some(state.to_string()),
+    }
+}
+
+/// The NYT board: rows are `role="group"` with tiles marked `data-testid="tile"` whose `data-state` is the evaluation.
+pub struct NytBoard;
+
+impl BoardAdapter for NytBoard {
+    fn read_rows(&self) -> Result<Vec<Row>, BoardError> {
+        let board = document()?.select("[class^='Board-module_board']")?;
+        let rows = board.select_all("[role='group']").into_iter().map(|row| {
+            let tiles = row.select_all("[data-testid='tile']");
+            let word: String = tiles
+                .iter()
+                .filter_map(|tile| tile.text_content())
+                .collect();
+            let evaluations = tiles
+                .iter()
+                .map(|tile| {
+                    tile.get_attribute("data-state")
+                        .and_then(|s| nyt_evaluation(&s))
+                })
+                .collect();
+
+            (word.trim().to_uppercase(), evaluations)
+        });
+
+        Ok(rows.collect())
+    }
+
+    fn press_key(&self, key: Key) -> Result<(), BoardError> {
+        let name = data_key(key);
+        click(
+            document()?.select(&format!("button[data-key='{}']", name))?,
+            &name,
+        )
+    }
+}
+
+/// Wordle Unlimited marks tiles with `letter-correct`, `letter-elsewhere` or `letter-absent` classes once they're submitted.
+fn unlimited_evaluation(class: &str) -> Option<String> {
+    class.split_whitespace().find_map(|class| match class {
+        "letter-correct" => Some("correct".to_string()),
+        "letter-elsewhere" => Some("present".to_string()),
+        "letter-absent" => Some("absent".to_string()),
+        _ => None,
+    })
+}
+
+/// Wordle Unlimited's board: `.Row`s of `.Row-letter`s, and a keyboard of `.Game-keyboard-button`s labelled with their key.
+pub struct UnlimitedBoard;
+
+impl BoardAdapter for UnlimitedBoard {
+    fn read_rows(&self) -> Result<Vec<Row>, BoardError> {
+        let rows = document()?.select_all(".Row").into_iter().map(|row| {
+            let tiles = row.select_all(".Row-letter");
+            let word: String = tiles
+                .iter()
+                .filter_map(|tile| tile.text_content())
+                .collect();
+            let evaluations = tiles
+                .iter()
+                .map(|tile| unlimited_evaluation(&tile.get_attribute("class").unwrap_or_default()))
+                .collect();
+
+            (word.trim().to_uppercase(), evaluations)
+        });
+
+        Ok(rows.collect())
+    }
+
+    fn press_key(&self, key: Key) -> Result<(), BoardError> {
+        let name = match key {
+            Key::Letter(letter) => letter.to_string(),
+            Key::Enter => "Enter".to_string(),
+            Key::Backspace => "Backspace".to_string(),
+        };
+
+        let button = document()?
+            .select_all(".Game-keyboard-button")
+            .into_iter()
+            .find(|button| {
+                button
+                    .text_content()
+                    .is_some_and(|text| text.trim().eq_ignore_ascii_case(&name))
+            })
+            .ok_or_else(|| BoardError::Missing(format!("the {} key", name)))?;
+
+        click(button, &name)
+    }
+}
+
+/// Reads a Quordle cell's label, e.g. `'R' (letter 1) is in a different spot`, into its letter and evaluation. Cells that haven't been submitted have no verdict.
+fn quordle_cell(label: &str) -> (Option<char>, Option<String>) {
+    let letter = label
+        .split('\'')
+        .nth(1)
+        .and_then(|letter| letter.chars().next())
+        .filter(|letter| letter.is_ascii_alphabetic());
+
+    let evaluation = if label.ends_with("is correct") || label.ends_with("is correct.") {
+        Some("correct")
+    } else if label.contains("is in a different spot") {
+        Some("present")
+    } else if label.contains("is incorrect") {
+        Some("absent")
+    } else {
+        None
+    };
+
+    (letter, evaluation.map(|e| e.to_string()))
+}
+
+/// Quordle's four boards are `role="table"`s of `role="row"`s and `role="cell"`s, described by their ARIA labels.
+pub struct QuordleBoard;
+
+impl QuordleBoard {
+    fn rows(board: &Element) -> Vec<Row> {
+        board
+            .select_all("[role='row']")
+            .into_iter()
+            .map(|row| {
+                let cells: Vec<(Option<char>, Option<String>)> = row
+                    .select_all("[role='cell']")
+                    .into_iter()
+                    .map(|cell| quordle_cell(&cell.get_attribute("aria-label").unwrap_or_default()))
+                    .collect();
+                let word: String = cells.iter().filter_map(|(letter, _)| *letter).collect();
+                let evaluations = cells
+                    .into_iter()
+                    .map(|(_, evaluation)| evaluation)
+                    .collect();
+
+                (word.to_uppercase(), evaluations)
+            })
+            .collect()
+    }
+}
+
+impl BoardAdapter for QuordleBoard {
+    /// The rows of the first board that isn't solved yet, or of the last board once they all are.
+    fn read_rows(&self) -> Result<Vec<Row>, BoardError> {
+        let boards = document()?.select_all("[role='table']");
+        let mut last = None;
+
+        for board in &boards {
+            let rows = QuordleBoard::rows(board);
+            let solved = session_from_rows(rows.clone())?
+                .guesses
+                .last()
+                .is_some_and(|guess| guess.results.chars().all(|c| c == 'X'));
+
+            if !solved {
+                return Ok(rows);
+            }
+            last = Some(rows);
+        }
+
+        last.ok_or_else(|| BoardError::Missing("[role='table']".to_string()))
+    }
+
+    fn max_guesses(&self) -> usize {
+        9
+    }
+
+    fn press_key(&self, key: Key) -> Result<(), BoardError> {
+        let name = match key {
+            Key::Letter(letter) => letter.to_ascii_uppercase().to_string(),
+            Key::Enter => "Enter".to_string(),
+            Key::Backspace => "Backspace".to_string(),
+        };
+
+        click(
+            document()?.select(&format!("button[aria-label='{}']", name))?,
+            &name,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn picks_sites_by_url() {
+        assert_eq!(
+            Some(Site::Nyt),
+            Site::from_url("https://www.nytimes.com/games/wordle/index.html")
+        );
+        assert_eq!(
+            None,
+            Site::from_url("https://www.nytimes.com/section/world")
+        );
+        assert_eq!(
+            Some(Site::Original),
+            Site::from_url("https://www.powerlanguage.co.uk/wordle/")
+        );
+        assert_eq!(
+            Some(Site::Quordle),
+            Site::from_url("https://www.quordle.com/#/")
+        );
+        assert_eq!(
+            Some(Site::Quordle),
+            Site::from_url("https://www.merriam-webster.com/games/quordle/")
+        );
+        assert_eq!(
+            Some(Site::WordleUnlimited),
+            Site::from_url("https://wordleunlimited.org/")
+        );
+        assert_eq!(None, Site::from_url("https://notnytimes.com/games/wordle"));
+    }
+
+    #[test]
+    fn translates_site_evaluations() {
+        assert_eq!(Some("correct".to_string()), nyt_evaluation("correct"));
+        assert_eq!(None, nyt_evaluation("tbd"));
+
+        assert_eq!(
+            Some("present".to_string()),
+            unlimited_evaluation("Row-letter letter-elsewhere")
+        );
+        assert_eq!(None, unlimited_evaluation("Row-letter"));
+
+        assert_eq!(
+            (Some('R'), Some("present".to_string())),
+            quordle_cell("'R' (letter 1) is in a different spot")
+        );
+        assert_eq!(
+            (Some('Y'), Some("correct".to_string())),
+            quordle_cell("'Y' (letter 5) is correct")
+        );
+        assert_eq!((None, None), quordle_cell("Blank (letter 2)"));
+    }
+}
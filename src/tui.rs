@@ -0,0 +1,445 @@
+use crate::cli::Options;
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::style::{Color, Print, ResetColor, SetBackgroundColor, SetForegroundColor};
+use crossterm::{cursor, execute, queue, terminal};
+use helple::{LetterStatus, Puzzle, Suggestion};
+use std::error::Error;
+use std::io::{self, Write};
+use std::panic;
+use std::time::Duration;
+
+const KEYBOARD: [&str; 3] = ["QWERTYUIOP", "ASDFGHJKL", "ZXCVBNM"];
+const BOARD_ROWS: usize = 6;
+const PANEL_COLUMN: u16 = 40;
+/// How long keys must stop coming before the preview is worked out, since `entropy` can take a while.
+const PREVIEW_DELAY: Duration = Duration::from_millis(200);
+
+const HELP: &str = "Letters type a word · Space/↑/↓ change a tile · ←/→ move · Tab next suggestion · Enter submit · Ctrl-Z undo · Ctrl-R reset · Esc quit";
+
+/// The terminal colours of each status, matching the 🟩🟨⬜ tiles printed by `LetterStatus`'s `Display`.
+fn colours(status: LetterStatus) -> (Color, Color) {
+    match status {
+        LetterStatus::Correct => (Color::Black, Color::Green),
+        LetterStatus::WrongSpot => (Color::Black, Color::Yellow),
+        LetterStatus::NotInWord => (Color::Black, Color::White),
+    }
+}
+
+/// The order tiles cycle through as they are changed.
+fn next_status(status: LetterStatus) -> LetterStatus {
+    match status {
+        LetterStatus::NotInWord => LetterStatus::WrongSpot,
+        LetterStatus::WrongSpot => LetterStatus::Correct,
+        LetterStatus::Correct => LetterStatus::NotInWord,
+    }
+}
+
+fn previous_status(status: LetterStatus) -> LetterStatus {
+    next_status(next_status(status))
+}
+
+/// What the puzzle would look like if the row being edited were submitted as it stands.
+struct Preview {
+    candidates: usize,
+    suggestions: Vec<Suggestion>,
+}
+
+/// Everything drawn on screen: the puzzle so far plus the row being edited.
+struct Board {
+    puzzle: Puzzle,
+    top: usize,
+    word: Vec<char>,
+    statuses: Vec<LetterStatus>,
+    cursor: usize,
+    /// Suggestions for the row being edited, which Tab cycles through.
+    choices: Vec<Suggestion>,
+    choice: usize,
+    candidates: usize,
+    preview: Preview,
+    /// The row changed since the preview was worked out, see `event_loop`.
+    preview_stale: bool,
+    message: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+enum Action {
+    Continue,
+    /// A guess was added or removed, so the session should be saved.
+    Changed,
+    Quit,
+}
+
+impl Board {
+    fn new(puzzle: Puzzle, top: usize) -> Board {
+        let mut board = Board {
+            puzzle,
+            top,
+            word: vec![],
+            statuses: vec![],
+            cursor: 0,
+            choices: vec![],
+            choice: 0,
+            candidates: 0,
+            preview: Preview {
+                candidates: 0,
+                suggestions: vec![],
+            },
+            preview_stale: false,
+            message: None,
+        };
+        board.next_row();
+        board
+    }
+
+    fn length(&self) -> usize {
+        self.puzzle.settings().word_length
+    }
+
+    fn solved(&self) -> bool {
+        self.puzzle
+            .guesses()
+            .last()
+            .is_some_and(|(_, statuses)| statuses.iter().all(|s| *s == LetterStatus::Correct))
+    }
+
+    /// Starts a fresh row with helple's suggestion in it.
+    fn next_row(&mut self) {
+        self.choices = self.puzzle.suggestions(self.top);
+        self.choice = 0;
+        self.candidates = self.puzzle.candidates().len();
+        self.word = match self.choices.first() {
+            Some(suggestion) => suggestion.word.chars().collect(),
+            None => vec![' '; self.length()],
+        };
+        self.statuses = vec![LetterStatus::NotInWord; self.length()];
+        self.cursor = 0;
+        self.preview_stale = true;
+    }
+
+    fn row_word(&self) -> String {
+        self.word.iter().collect()
+    }
+
+    fn update_preview(&mut self) {
+        self.preview_stale = false;
+        let word = self.row_word();
+        if !self.puzzle.dictionary().contains(&word) || self.solved() {
+            self.preview = Preview {
+                candidates: self.candidates,
+                suggestions: vec![],
+            };
+            return;
+        }
+
+        let mut puzzle = self.puzzle.clone();
+        puzzle.assign_guess_results(word, self.statuses.clone());
+        self.preview = Preview {
+            candidates: puzzle.candidates().len(),
+            suggestions: puzzle.suggestions(self.top),
+        };
+    }
+
+    fn handle(&mut self, key: KeyEvent) -> Action {
+        let control = key.modifiers.contains(KeyModifiers::CONTROL);
+        self.message = None;
+
+        match key.code {
+            KeyCode::Esc => return Action::Quit,
+            KeyCode::Char('c') if control => return Action::Quit,
+            KeyCode::Char('z') if control => {
+                match self.puzzle.undo() {
+                    Some((word, _)) => self.message = Some(format!("Forgot {}.", word)),
+                    None => self.message = Some("Nothing to undo.".to_string()),
+                }
+                self.next_row();
+                return Action::Changed;
+            }
+            KeyCode::Char('r') if control => {
+                self.puzzle.reset();
+                self.message = Some("Starting over.".to_string());
+                self.next_row();
+                return Action::Changed;
+            }
+            _ if self.solved() => return Action::Continue,
+            KeyCode::Enter => return self.submit(),
+            KeyCode::Left => self.cursor = self.cursor.saturating_sub(1),
+            KeyCode::Right => self.cursor = (self.cursor + 1).min(self.length() - 1),
+            KeyCode::Backspace => {
+                self.cursor = self.cursor.saturating_sub(1);
+                return Action::Continue;
+            }
+            KeyCode::Char(' ') | KeyCode::Up => {
+                self.statuses[self.cursor] = next_status(self.statuses[self.cursor])
+            }
+            KeyCode::Down => {
+                self.statuses[self.cursor] = previous_status(self.statuses[self.cursor])
+            }
+            KeyCode::Tab if !self.choices.is_empty() => {
+                self.choice = (self.choice + 1) % self.choices.len();
+                self.word = self.choices[self.choice].word.chars().collect();
+            }
+            KeyCode::Char(c) if c.is_ascii_alphabetic() => {
+                self.word[self.cursor] = c.to_ascii_uppercase();
+                self.cursor = (self.cursor + 1).min(self.length() - 1);
+            }
+            _ => return Action::Continue,
+        }
+
+        self.preview_stale = true;
+        Action::Continue
+    }
+
+    fn submit(&mut self) -> Action {
+        let word = self.row_word();
+        if !self.puzzle.dictionary().contains(&word) {
+            self.message = Some(format!("<{}> isn't in the word list.", word.trim()));
+            return Action::Continue;
+        }
+
+        self.puzzle
+            .assign_guess_results(word, self.statuses.clone());
+        self.next_row();
+
+        if self.solved() {
+            self.message = Some(format!(
+                "Puzzle solved in {}! 🙌 Press Esc to quit.",
+                self.puzzle.guesses().len()
+            ));
+        } else if self.candidates == 0 {
+            self.message =
+                Some("No candidates left. 💥 Ctrl-Z to undo a mistyped result.".to_string());
+        }
+
+        Action::Changed
+    }
+
+    /// The best-known status of `letter` across every guess so far, like the keyboard in Wordle.
+    fn keyboard_status(&self, letter: char) -> Option<LetterStatus> {
+        let mut best = None;
+
+        for (word, statuses) in self.puzzle.guesses() {
+            for (c, status) in word.chars().zip(statuses) {
+                if c != letter {
+                    continue;
+                }
+                best = match (best, *status) {
+                    (Some(LetterStatus::Correct), _) | (_, LetterStatus::Correct) => {
+                        Some(LetterStatus::Correct)
+                    }
+                    (Some(LetterStatus::WrongSpot), _) | (_, LetterStatus::WrongSpot) => {
+                        Some(LetterStatus::WrongSpot)
+                    }
+                    _ => Some(LetterStatus::NotInWord),
+                };
+            }
+        }
+
+        best
+    }
+}
+
+fn tile<W: Write>(out: &mut W, letter: char, status: Option<LetterStatus>) -> io::Result<()> {
+    match status {
+        Some(status) => {
+            let (foreground, background) = colours(status);
+            queue!(
+                out,
+                SetForegroundColor(foreground),
+                SetBackgroundColor(background),
+                Print(format!(" {} ", letter)),
+                ResetColor,
+                Print(" ")
+            )
+        }
+        None => queue!(out, Print(format!("[{}] ", letter))),
+    }
+}
+
+fn draw<W: Write>(out: &mut W, board: &Board) -> io::Result<()> {
+    queue!(out, terminal::Clear(terminal::ClearType::All))?;
+
+    let guesses = board.puzzle.guesses();
+    // Keep the row being edited on screen once there are more guesses than the board has rows.
+    let skip = (guesses.len() + 1).saturating_sub(BOARD_ROWS);
+    let mut row = 1;
+
+    for (word, statuses) in guesses.iter().skip(skip) {
+        queue!(out, cursor::MoveTo(2, row))?;
+        for (letter, status) in word.chars().zip(statuses) {
+            tile(out, letter, Some(*status))?;
+        }
+        row += 2;
+    }
+
+    let editing = if board.solved() {
+        None
+    } else {
+        queue!(out, cursor::MoveTo(2, row))?;
+        for (letter, status) in board.word.iter().zip(&board.statuses) {
+            tile(out, *letter, Some(*status))?;
+        }
+        Some(row)
+    };
+
+    row = 1 + 2 * BOARD_ROWS as u16 + 1;
+    for (indent, keys) in KEYBOARD.iter().enumerate() {
+        queue!(out, cursor::MoveTo(2 + 2 * indent as u16, row))?;
+        for letter in keys.chars() {
+            tile(out, letter, board.keyboard_status(letter))?;
+        }
+        row += 2;
+    }
+
+    let (preview, suggestions) = if board.preview_stale {
+        ("…".to_string(), &[][..])
+    } else {
+        (
+            board.preview.candidates.to_string(),
+            &board.preview.suggestions[..],
+        )
+    };
+    queue!(
+        out,
+        cursor::MoveTo(PANEL_COLUMN, 1),
+        Print(format!("Candidates: {}", board.candidates)),
+        cursor::MoveTo(PANEL_COLUMN, 2),
+        Print(format!("With this row: {}", preview)),
+        cursor::MoveTo(PANEL_COLUMN, 4),
+        Print("Then try:")
+    )?;
+    for (i, suggestion) in suggestions.iter().enumerate() {
+        queue!(
+            out,
+            cursor::MoveTo(PANEL_COLUMN + 2, 5 + i as u16),
+            Print(format!("{:<8}{:>8.2}", suggestion.word, suggestion.score))
+        )?;
+    }
+
+    row += 1;
+    queue!(out, cursor::MoveTo(2, row), Print(HELP))?;
+    if let Some(message) = &board.message {
+        queue!(out, cursor::MoveTo(2, row + 2), Print(message))?;
+    }
+
+    match editing {
+        Some(editing) => queue!(
+            out,
+            cursor::MoveTo(2 + 4 * board.cursor as u16 + 1, editing),
+            cursor::Show
+        )?,
+        None => queue!(out, cursor::Hide)?,
+    }
+
+    out.flush()
+}
+
+/// Runs the full-screen solver until the player quits.
+pub fn run(options: &Options) -> Result<(), Box<dyn Error>> {
+    let puzzle = crate::puzzle(options, &options.args)?;
+    let mut board = Board::new(puzzle, options.top.max(5));
+    let mut out = io::stdout();
+
+    terminal::enable_raw_mode()?;
+    execute!(out, terminal::EnterAlternateScreen)?;
+
+    // Put the terminal back before a panic's message is printed, or it's lost in the alternate screen.
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        let _ = restore_terminal();
+        default_hook(info);
+    }));
+
+    let result = event_loop(&mut out, &mut board, options);
+
+    drop(panic::take_hook());
+    restore_terminal()?;
+
+    result
+}
+
+fn restore_terminal() -> io::Result<()> {
+    execute!(io::stdout(), cursor::Show, terminal::LeaveAlternateScreen)?;
+    terminal::disable_raw_mode()
+}
+
+fn event_loop<W: Write>(
+    out: &mut W,
+    board: &mut Board,
+    options: &Options,
+) -> Result<(), Box<dyn Error>> {
+    loop {
+        draw(out, board)?;
+
+        if board.preview_stale && !event::poll(PREVIEW_DELAY)? {
+            board.update_preview();
+            continue;
+        }
+
+        let key = match event::read()? {
+            Event::Key(key) if key.kind != KeyEventKind::Release => key,
+            _ => continue,
+        };
+
+        match board.handle(key) {
+            Action::Continue => {}
+            Action::Changed => {
+                crate::save(options, &board.puzzle)?;
+                crate::record_solution(options, &board.puzzle)?;
+            }
+            Action::Quit => return Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn press(board: &mut Board, code: KeyCode) -> Action {
+        board.handle(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    #[test]
+    fn edits_and_submits_rows() {
+        let mut board = Board::new(Puzzle::setup(), 5);
+        assert_eq!("RUSTY", board.row_word());
+        board.update_preview();
+        let before = board.preview.candidates;
+
+        // ---.X
+        for _ in 0..3 {
+            press(&mut board, KeyCode::Right);
+        }
+        press(&mut board, KeyCode::Char(' '));
+        press(&mut board, KeyCode::Right);
+        press(&mut board, KeyCode::Up);
+        press(&mut board, KeyCode::Up);
+        assert_eq!("---.X", helple::format_feedback(&board.statuses));
+        // The preview waits for the keys to stop.
+        assert!(board.preview_stale);
+        assert_eq!(before, board.preview.candidates);
+        board.update_preview();
+        assert!(board.preview.candidates < before);
+
+        let expected = board.preview.candidates;
+        assert_eq!(Action::Changed, press(&mut board, KeyCode::Enter));
+        assert_eq!(expected, board.candidates);
+        assert_eq!(Some(LetterStatus::Correct), board.keyboard_status('Y'));
+        assert_eq!(Some(LetterStatus::WrongSpot), board.keyboard_status('T'));
+        assert_eq!(Some(LetterStatus::NotInWord), board.keyboard_status('R'));
+        assert_eq!(None, board.keyboard_status('A'));
+    }
+
+    #[test]
+    fn typed_words_must_be_in_the_word_list() {
+        let mut board = Board::new(Puzzle::setup(), 5);
+        for c in "QWXYZ".chars() {
+            press(&mut board, KeyCode::Char(c));
+        }
+
+        assert_eq!("QWXYZ", board.row_word());
+        assert_eq!(Action::Continue, press(&mut board, KeyCode::Enter));
+        assert!(board.puzzle.guesses().is_empty());
+        assert!(board.message.is_some());
+    }
+}
@@ -0,0 +1,32 @@
+static DICTIONARY: &str = include_str!("../support/less-words");
+
+/// `support/less-words` starts with the original game's answer list, in the order the answers were scheduled ("cigar", "rebut", "sissy", ...). Everything after it is only accepted as a guess.
+const ANSWER_COUNT: usize = 2315;
+
+/// Every five letter word Wordle accepts as a guess, uppercased.
+pub fn dictionary() -> Vec<String> {
+    parse(DICTIONARY, 5)
+}
+
+/// Reads a word list with one word per line, keeping the words of the given length, uppercased.
+pub fn parse(input: &str, length: usize) -> Vec<String> {
+    let mut dictionary: Vec<String> = Vec::new();
+
+    for word in input.split('\n') {
+        let trimmed = word.trim();
+        if trimmed.len() != length || !trimmed.chars().all(|c| c.is_ascii_alphabetic()) {
+            continue;
+        }
+
+        dictionary.push(trimmed.to_uppercase());
+    }
+
+    dictionary
+}
+
+/// The words that can be the answer to a puzzle, uppercased and in their original order.
+pub fn answers() -> Vec<String> {
+    let mut answers = dictionary();
+    answers.truncate(ANSWER_COUNT);
+    answers
+}
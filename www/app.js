@@ -0,0 +1,149 @@
+// The board is kept as rows of a word and one result per letter, typed as on the command line: X correct, . wrong spot, - not in the word.
+const LENGTH = 5;
+const ROWS = 6;
+const SUGGESTIONS = 5;
+
+const NEXT_RESULT = { "-": ".", ".": "X", "X": "-" };
+const RESULT_CLASS = { "-": "absent", ".": "present", "X": "correct" };
+
+const worker = new HelpleWorker("worker.js");
+
+let rows = [];
+let typing = "";
+let job = null;
+
+// The state the solver reads, shaped like a `Session`.
+function session() {
+    return {
+        settings: {
+            strategy: document.getElementById("strategy").value,
+            hard_mode: document.getElementById("hard_mode").checked,
+        },
+        guesses: rows.map(row => ({ word: row.word, results: row.results.join("") })),
+    };
+}
+
+function render_board() {
+    let board = document.getElementById("board");
+    board.replaceChildren();
+
+    for (let r = 0; r < ROWS; r++) {
+        let row = document.createElement("div");
+        row.className = "row";
+
+        for (let i = 0; i < LENGTH; i++) {
+            let tile = document.createElement("div");
+            tile.className = "tile";
+
+            if (r < rows.length) {
+                let result = rows[r].results[i];
+                tile.textContent = rows[r].word[i];
+                tile.classList.add(RESULT_CLASS[result]);
+                tile.title = "Click to change colour";
+                tile.addEventListener("click", () => {
+                    rows[r].results[i] = NEXT_RESULT[result];
+                    update();
+                });
+            } else if (r == rows.length && i < typing.length) {
+                tile.textContent = typing[i];
+                tile.classList.add("typed");
+            }
+
+            row.appendChild(tile);
+        }
+        board.appendChild(row);
+    }
+}
+
+function render_report(report) {
+    document.getElementById("error").textContent = report.error || "";
+    document.getElementById("count").textContent =
+        report.candidate_count == 1 ? "1 word left." : `${report.candidate_count} words left.`;
+
+    document.getElementById("suggestions").replaceChildren(...report.suggestions.map(suggestion => {
+        let item = document.createElement("li");
+        let score = document.createElement("span");
+        score.className = "score";
+        score.textContent = ` ${suggestion.score.toFixed(2)}`;
+        item.append(suggestion.word, score);
+        item.title = "Click to play it";
+        item.addEventListener("click", () => {
+            typing = suggestion.word;
+            render_board();
+        });
+        return item;
+    }));
+
+    document.getElementById("remaining").textContent = report.remaining.join(" ");
+}
+
+// Asks the worker for suggestions, abandoning any it is still working on.
+function refresh() {
+    if (job) {
+        job.cancel();
+    }
+
+    let progress = document.getElementById("progress");
+    progress.value = 0;
+    progress.hidden = false;
+
+    let current = worker.suggest(session(), [], SUGGESTIONS, value => progress.value = value);
+    job = current;
+    current.result.then(
+        report => {
+            if (job == current) {
+                progress.hidden = true;
+                render_report(report);
+            }
+        },
+        error => {
+            if (job == current && error != "cancelled") {
+                progress.hidden = true;
+                document.getElementById("error").textContent = error;
+            }
+        },
+    );
+}
+
+function update() {
+    render_board();
+    refresh();
+}
+
+document.addEventListener("keydown", event => {
+    if (event.ctrlKey || event.metaKey || event.altKey || event.target.tagName == "SELECT") {
+        return;
+    }
+    // Enter on a focused button presses the button instead.
+    if (event.key == "Enter" && event.target.tagName == "BUTTON") {
+        return;
+    }
+
+    if (/^[a-z]$/i.test(event.key) && typing.length < LENGTH && rows.length < ROWS) {
+        typing += event.key.toUpperCase();
+        render_board();
+    } else if (event.key == "Backspace") {
+        typing = typing.slice(0, -1);
+        render_board();
+    } else if (event.key == "Enter" && typing.length == LENGTH) {
+        rows.push({ word: typing, results: Array(LENGTH).fill("-") });
+        typing = "";
+        update();
+    }
+});
+
+document.getElementById("undo").addEventListener("click", () => {
+    rows.pop();
+    update();
+});
+
+document.getElementById("reset").addEventListener("click", () => {
+    rows = [];
+    typing = "";
+    update();
+});
+
+document.getElementById("strategy").addEventListener("change", refresh);
+document.getElementById("hard_mode").addEventListener("change", refresh);
+
+update();
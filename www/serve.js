@@ -0,0 +1,35 @@
+// Serves this directory at http://localhost:8080 (or $PORT). Browsers won't load wasm or workers from file:// URLs, so the page needs a server, but not much of one.
+const http = require("http");
+const fs = require("fs");
+const path = require("path");
+
+const PORT = process.env.PORT || 8080;
+const ROOT = __dirname;
+
+const TYPES = {
+    ".html": "text/html; charset=utf-8",
+    ".js": "text/javascript; charset=utf-8",
+    ".css": "text/css; charset=utf-8",
+    ".wasm": "application/wasm",
+};
+
+http.createServer((request, response) => {
+    let url = new URL(request.url, `http://${request.headers.host}`);
+    let file = path.join(ROOT, path.normalize(decodeURIComponent(url.pathname)));
+    if (!file.startsWith(ROOT)) {
+        response.writeHead(403).end();
+        return;
+    }
+    if (file.endsWith(path.sep)) {
+        file = path.join(file, "index.html");
+    }
+
+    fs.readFile(file, (error, data) => {
+        if (error) {
+            response.writeHead(404).end("Not found");
+            return;
+        }
+        response.writeHead(200, { "Content-Type": TYPES[path.extname(file)] || "application/octet-stream" });
+        response.end(data);
+    });
+}).listen(PORT, () => console.log(`helple is at http://localhost:${PORT}/ (run \`cargo xtask build\` first)`));
@@ -0,0 +1,327 @@
+use std::collections::BTreeSet;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{self, Command};
+
+const USAGE: &str = r#"Usage: cargo xtask <command>
+
+Commands:
+  check               Check every wasm_bindgen.<export> the JavaScript uses is exported by src/lib.rs
+  build               Build the wasm module and copy it, with its JavaScript glue, into extension/ and www/
+  package [BROWSER]   Build, then lay out an unpacked extension per browser in dist/ (chrome, firefox, safari)"#;
+
+const BROWSERS: [&str; 3] = ["chrome", "firefox", "safari"];
+
+/// Folders whose scripts call the wasm module through `wasm_bindgen`.
+const SCRIPT_DIRS: [&str; 2] = ["extension", "www"];
+
+/// Scripts `build` copies from extension/ so the web page runs the same worker.
+const SHARED_SCRIPTS: [&str; 2] = ["worker.js", "worker-client.js"];
+
+/// Files `build` writes, which aren't sources of their own.
+const GENERATED: [&str; 2] = ["helple.js", "helple_bg.wasm"];
+
+const HEADER: &str = "// Generated by `cargo xtask build` from wasm-bindgen's output. Don't edit; change src/lib.rs and rebuild.\n";
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    let result = match args
+        .iter()
+        .map(|a| a.as_str())
+        .collect::<Vec<_>>()
+        .as_slice()
+    {
+        ["check"] => check(),
+        ["build"] => build(),
+        ["package"] => package(&BROWSERS),
+        ["package", browser] if BROWSERS.contains(browser) => package(&[browser]),
+        _ => {
+            eprintln!("{}", USAGE);
+            process::exit(2);
+        }
+    };
+
+    if let Err(err) = result {
+        eprintln!("error: {}", err);
+        process::exit(1);
+    }
+}
+
+/// The repository root, one up from this crate.
+fn root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("xtask lives inside the repository")
+        .to_path_buf()
+}
+
+/// Names exported to JavaScript by top level `#[wasm_bindgen]` items in `source`. Methods inside a `#[wasm_bindgen] impl` belong to their struct, so aren't counted.
+fn rust_exports(source: &str) -> BTreeSet<String> {
+    let mut exports = BTreeSet::new();
+    let mut pending = false;
+
+    for line in source.lines() {
+        if line.starts_with("#[wasm_bindgen") {
+            pending = true;
+            continue;
+        }
+        if !pending || line.starts_with("#[") || line.starts_with("///") {
+            continue;
+        }
+
+        pending = false;
+        let item = ["pub fn ", "pub struct "]
+            .iter()
+            .find_map(|keyword| line.strip_prefix(keyword));
+        if let Some(item) = item {
+            exports.insert(identifier(item));
+        }
+    }
+
+    exports
+}
+
+/// Names exported by wasm-bindgen's TypeScript declarations.
+fn generated_exports(declarations: &str) -> BTreeSet<String> {
+    declarations
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            ["export function ", "export class "]
+                .iter()
+                .find_map(|keyword| line.strip_prefix(keyword))
+        })
+        .map(identifier)
+        .collect()
+}
+
+/// Every `wasm_bindgen.<name>` a script uses.
+fn js_uses(script: &str) -> BTreeSet<String> {
+    script
+        .split("wasm_bindgen.")
+        .skip(1)
+        .map(identifier)
+        .filter(|name| !name.is_empty())
+        .collect()
+}
+
+fn identifier(s: &str) -> String {
+    s.chars()
+        .take_while(|c| c.is_alphanumeric() || *c == '_')
+        .collect()
+}
+
+/// Scripts under `SCRIPT_DIRS`, leaving out generated glue.
+fn scripts(root: &Path) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+    let mut scripts = Vec::new();
+
+    for dir in SCRIPT_DIRS {
+        for entry in fs::read_dir(root.join(dir))? {
+            let path = entry?.path();
+            let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+            let copied = dir != "extension" && SHARED_SCRIPTS.contains(&name);
+
+            if name.ends_with(".js") && !GENERATED.contains(&name) && !copied {
+                scripts.push(path);
+            }
+        }
+    }
+
+    scripts.sort();
+    Ok(scripts)
+}
+
+/// Each use of an export in `scripts` that `exports` doesn't have, e.g. `www/app.js uses wasm_bindgen.sugest`.
+fn missing_exports(scripts: &[(String, String)], exports: &BTreeSet<String>) -> Vec<String> {
+    scripts
+        .iter()
+        .flat_map(|(name, script)| {
+            js_uses(script)
+                .into_iter()
+                .filter(|used| !exports.contains(used))
+                .map(move |used| format!("{} uses wasm_bindgen.{}", name, used))
+        })
+        .collect()
+}
+
+fn check() -> Result<(), Box<dyn Error>> {
+    let root = root();
+    let exports = rust_exports(&fs::read_to_string(root.join("src/lib.rs"))?);
+
+    let mut sources = Vec::new();
+    for path in scripts(&root)? {
+        let name = path.strip_prefix(&root)?.display().to_string();
+        sources.push((name, fs::read_to_string(&path)?));
+    }
+
+    let missing = missing_exports(&sources, &exports);
+    if !missing.is_empty() {
+        return Err(format!(
+            "src/lib.rs doesn't export what the scripts use:\n  {}",
+            missing.join("\n  ")
+        )
+        .into());
+    }
+
+    println!(
+        "{} scripts only use the {} exports from src/lib.rs.",
+        sources.len(),
+        exports.len()
+    );
+    Ok(())
+}
+
+fn run(command: &mut Command) -> Result<(), Box<dyn Error>> {
+    let status = command.status().map_err(|err| {
+        format!(
+            "Couldn't run {:?} ({}). Is it installed?",
+            command.get_program(),
+            err
+        )
+    })?;
+
+    if !status.success() {
+        return Err(format!("{:?} failed with {}", command.get_program(), status).into());
+    }
+    Ok(())
+}
+
+fn build() -> Result<(), Box<dyn Error>> {
+    check()?;
+
+    let root = root();
+    run(Command::new("wasm-pack")
+        .args(["build", "--target", "no-modules"])
+        .current_dir(&root))?;
+
+    let pkg = root.join("pkg");
+    let expected = rust_exports(&fs::read_to_string(root.join("src/lib.rs"))?);
+    let generated = generated_exports(&fs::read_to_string(pkg.join("helple.d.ts"))?);
+    if generated != expected {
+        return Err(format!(
+            "wasm-bindgen exported {:?} but src/lib.rs has {:?}",
+            generated, expected
+        )
+        .into());
+    }
+
+    let glue = format!("{}{}", HEADER, fs::read_to_string(pkg.join("helple.js"))?);
+    for dir in SCRIPT_DIRS {
+        fs::write(root.join(dir).join("helple.js"), &glue)?;
+        fs::copy(
+            pkg.join("helple_bg.wasm"),
+            root.join(dir).join("helple_bg.wasm"),
+        )?;
+    }
+    for script in SHARED_SCRIPTS {
+        fs::copy(
+            root.join("extension").join(script),
+            root.join("www").join(script),
+        )?;
+    }
+
+    println!("Copied the wasm module and its glue into extension/ and www/.");
+    Ok(())
+}
+
+/// Copies `from` into `to`, leaving out `skip`.
+fn copy_dir(from: &Path, to: &Path, skip: &str) -> Result<(), Box<dyn Error>> {
+    fs::create_dir_all(to)?;
+
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        if entry.file_name() == skip {
+            continue;
+        }
+
+        let target = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &target, skip)?;
+        } else {
+            fs::copy(entry.path(), target)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn package(browsers: &[&str]) -> Result<(), Box<dyn Error>> {
+    build()?;
+
+    let root = root();
+    let extension = root.join("extension");
+
+    for browser in browsers {
+        let dist = root.join("dist").join(browser);
+        if dist.exists() {
+            fs::remove_dir_all(&dist)?;
+        }
+
+        copy_dir(&extension, &dist, "manifests")?;
+        fs::copy(
+            extension
+                .join("manifests")
+                .join(format!("{}.json", browser)),
+            dist.join("manifest.json"),
+        )?;
+        println!("Packaged {}", dist.strip_prefix(&root)?.display());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn finds_top_level_exports() {
+        let source = r#"
+/// Suggestions.
+#[wasm_bindgen]
+pub fn suggest(state: JsValue, count: usize) -> JsValue {
+
+#[wasm_bindgen]
+pub struct Suggester {
+
+#[wasm_bindgen]
+impl Suggester {
+    #[wasm_bindgen(constructor)]
+    pub fn new(state: JsValue) -> Suggester {
+
+fn read_words(words: JsValue) {
+"#;
+
+        let exports: Vec<String> = rust_exports(source).into_iter().collect();
+        assert_eq!(vec!["Suggester", "suggest"], exports);
+    }
+
+    #[test]
+    fn finds_drifted_uses() {
+        let exports = rust_exports("#[wasm_bindgen]\npub fn hints(state: JsValue) {}");
+        let scripts = vec![(
+            "popup.js".to_string(),
+            "wasm_bindgen.hints(wasm_bindgen.read_board());".to_string(),
+        )];
+
+        assert_eq!(
+            vec!["popup.js uses wasm_bindgen.read_board"],
+            missing_exports(&scripts, &exports)
+        );
+    }
+
+    #[test]
+    fn reads_generated_declarations() {
+        let declarations = r#"declare namespace wasm_bindgen {
+	export function suggest(state: any, count: number): any;
+	export class Suggester {
+	  free(): void;
+	}
+}"#;
+
+        let exports: Vec<String> = generated_exports(declarations).into_iter().collect();
+        assert_eq!(vec!["Suggester", "suggest"], exports);
+    }
+}